@@ -0,0 +1,149 @@
+//! Compiles the GLSL shaders to SPIR-V, so the crate doesn't need shaderc at
+//! runtime.
+//!
+//! Every `src/chapters/NN.shader.{vert,frag}` becomes a `VERTEX_NN` or
+//! `FRAGMENT_NN` constant in `$OUT_DIR/chapter_shaders.rs`, and the shaders
+//! in `tests/shaders` end up in `$OUT_DIR/test_shaders.rs` the same way.
+//! A shader that fails to compile fails the build with shaderc's
+//! `file:line: error` messages.
+
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use shaderc::{CompileOptions, Compiler, ShaderKind};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let mut compiler = Compiler::new().expect("Unable to initialize shaderc");
+    let options = CompileOptions::new().expect("Unable to initialize shaderc");
+
+    let mut errors = Vec::new();
+    for (dir, generated) in &[
+        ("src/chapters", "chapter_shaders.rs"),
+        ("tests/shaders", "test_shaders.rs"),
+    ] {
+        let dir = manifest_dir.join(dir);
+        println!("cargo:rerun-if-changed={}", dir.display());
+
+        let mut code = String::new();
+        for shader in shaders(&dir) {
+            println!("cargo:rerun-if-changed={}", shader.path.display());
+
+            match compile(&mut compiler, &options, &shader) {
+                Ok(spirv) => {
+                    let spv = out_dir.join(format!("{}.spv", shader.file_name));
+                    fs::write(&spv, spirv).unwrap();
+                    code += &shader.constant(&spv);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        fs::write(out_dir.join(generated), code).unwrap();
+    }
+
+    if !errors.is_empty() {
+        for err in &errors {
+            eprintln!("{}", err);
+        }
+        panic!("{} shader(s) failed to compile", errors.len());
+    }
+}
+
+struct Shader {
+    path: PathBuf,
+    file_name: String,
+    /// `NN` for `NN.shader.vert`, `name` for `name.vert`.
+    name: String,
+    kind: ShaderKind,
+    stage: &'static str,
+}
+
+impl Shader {
+    fn constant(&self, spv: &Path) -> String {
+        let mut code = String::new();
+        writeln!(
+            code,
+            "pub const {stage}_{name}: ShaderSource = ShaderSource {{
+    path: {path:?},
+    spirv: include_bytes!({spv:?}),
+    stage: ShaderStageFlags::{stage},
+}};
+",
+            stage = self.stage,
+            name = self.name.to_uppercase(),
+            path = self.path.display().to_string(),
+            spv = spv.display().to_string(),
+        )
+        .unwrap();
+        code
+    }
+}
+
+/// The shaders in `dir`, sorted so the generated code is stable.
+fn shaders(dir: &Path) -> Vec<Shader> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut shaders: Vec<_> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?.to_owned();
+            let (kind, stage) = match path.extension()?.to_str()? {
+                "vert" => (ShaderKind::Vertex, "VERTEX"),
+                "frag" => (ShaderKind::Fragment, "FRAGMENT"),
+                _ => return None,
+            };
+            let stem = path.file_stem()?.to_str()?;
+            let name = stem.trim_end_matches(".shader").to_owned();
+
+            Some(Shader {
+                path,
+                file_name,
+                name,
+                kind,
+                stage,
+            })
+        })
+        .collect();
+
+    shaders.sort_by(|a, b| a.path.cmp(&b.path));
+    shaders
+}
+
+fn compile(
+    compiler: &mut Compiler,
+    options: &CompileOptions,
+    shader: &Shader,
+) -> Result<Vec<u8>, String> {
+    let source = fs::read_to_string(&shader.path)
+        .map_err(|err| format!("{}: {}", shader.path.display(), err))?;
+
+    // shaderc prefixes its messages with this name, which gives the
+    // `file:line: error` format editors and terminals pick up.
+    let artifact = compiler
+        .compile_into_spirv(
+            &source,
+            shader.kind,
+            &shader.path.display().to_string(),
+            "main",
+            Some(options),
+        )
+        .map_err(|err| err.to_string())?;
+
+    if artifact.get_num_warnings() > 0 {
+        for warning in artifact.get_warning_messages().lines() {
+            println!("cargo:warning={}", warning);
+        }
+    }
+
+    Ok(artifact.as_binary_u8().to_vec())
+}
@@ -0,0 +1,379 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields, Ident, Lit,
+    Meta, NestedMeta, Result, Type,
+};
+
+/// Derives rendy's `AsVertex` for a `#[repr(C)]` struct with named fields.
+///
+/// Every field becomes one vertex attribute. The attribute name defaults to
+/// the field name and the format is inferred for `f32`, `u32` and `i32`
+/// scalars and arrays of up to four of them. Both can be overridden per field.
+/// Attributes are laid out back to back; `Shaders::check_vertex` reports
+/// fields that `#[repr(C)]` padding moved elsewhere.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+/// struct Vertex {
+///     position: [f32; 2],
+///     #[vertex(name = "color", format = "Rgba8Unorm")]
+///     tint: [u8; 4],
+/// }
+/// ```
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_vertex(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_vertex(input: DeriveInput) -> Result<TokenStream2> {
+    if !has_repr_c(&input.attrs) {
+        return Err(Error::new(
+            input.ident.span(),
+            "#[derive(Vertex)] requires #[repr(C)] so the field offsets match the attribute offsets",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "#[derive(Vertex)] requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "#[derive(Vertex)] can only be used on structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "#[derive(Vertex)] requires at least one field",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut attributes = Vec::new();
+    let mut definitions = TokenStream2::new();
+    let mut offsets = Vec::new();
+
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        offsets.push(quote!((&value.#field_ident as *const _ as usize - base) as u32));
+        let options = FieldOptions::parse(&field.attrs)?;
+
+        let name = options.name.unwrap_or_else(|| field_ident.to_string());
+        let format = match options.format {
+            Some(format) => format,
+            None => infer_format(&field.ty).ok_or_else(|| {
+                Error::new(
+                    field.ty.span(),
+                    "Unable to infer the vertex format, add #[vertex(format = \"...\")]",
+                )
+            })?,
+        };
+
+        let attribute = format_ident!("{}_{}", ident, field_ident);
+        let ty = &field.ty;
+
+        definitions.extend(quote! {
+            #[allow(dead_code, non_camel_case_types)]
+            #[repr(transparent)]
+            #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+            struct #attribute(#ty);
+
+            impl ::rendy::core::types::vertex::AsAttribute for #attribute {
+                const NAME: &'static str = #name;
+                const FORMAT: ::rendy::hal::format::Format = ::rendy::hal::format::Format::#format;
+            }
+        });
+
+        attributes.push(attribute);
+    }
+
+    // Nest the attributes as pairs, `(a, (b, c))`, which rendy accepts for any
+    // number of attributes.
+    let vertex = attributes
+        .iter()
+        .rev()
+        .map(|attribute| quote!(<#attribute as ::rendy::core::types::vertex::AsVertex>::vertex()))
+        .fold(None, |rest, attribute| match rest {
+            None => Some(attribute),
+            Some(rest) => Some(quote!((#attribute, #rest))),
+        })
+        .unwrap();
+
+    let krate = quote!(::glium_tutorial_but_its_rendy::vertex);
+
+    Ok(quote! {
+        const _: () = {
+            #definitions
+
+            impl #impl_generics ::rendy::core::types::vertex::AsVertex for #ident #ty_generics #where_clause {
+                fn vertex() -> ::rendy::core::types::vertex::VertexFormat {
+                    ::rendy::core::types::vertex::VertexFormat::new(#vertex)
+                }
+            }
+
+            impl #impl_generics #krate::VertexFields for #ident #ty_generics #where_clause {
+                fn field_offsets() -> ::std::vec::Vec<u32> {
+                    // Vertex attributes are plain data, so an all-zero value
+                    // is valid.
+                    let value: Self = unsafe { ::std::mem::zeroed() };
+                    let base = &value as *const Self as usize;
+                    vec![#(#offsets),*]
+                }
+            }
+        };
+    })
+}
+
+/// Derives `Uniform` for a `#[repr(C)]` struct with named fields, so it can be
+/// written to a `UniformBuffer`.
+///
+/// Members follow the `std140` rules unless the struct is marked
+/// `#[uniform(std430)]`. Every field type must implement `GlslType`, and so
+/// does the struct, so it can be nested in other uniforms.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, Uniform)]
+/// struct Locals {
+///     matrix: [[f32; 4]; 4],
+///     t: f32,
+/// }
+/// ```
+#[proc_macro_derive(Uniform, attributes(uniform))]
+pub fn derive_uniform(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_uniform(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_uniform(input: DeriveInput) -> Result<TokenStream2> {
+    if !has_repr_c(&input.attrs) {
+        return Err(Error::new(
+            input.ident.span(),
+            "#[derive(Uniform)] requires #[repr(C)] so the field offsets are predictable",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "#[derive(Uniform)] requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "#[derive(Uniform)] can only be used on structs",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "#[derive(Uniform)] does not support generic structs",
+        ));
+    }
+
+    let layout = block_layout(&input.attrs)?;
+    let ident = &input.ident;
+    let krate = quote!(::glium_tutorial_but_its_rendy::uniform);
+
+    let members = fields.iter().map(|field| {
+        let field_ident = field.ident.as_ref().unwrap();
+        let name = field_ident.to_string();
+        let ty = &field.ty;
+        quote! {
+            #krate::UniformMember {
+                name: #name,
+                offset: (&value.#field_ident as *const _ as usize - base) as u32,
+                align: <#ty as #krate::GlslType>::align(Self::LAYOUT),
+                size: <#ty as #krate::GlslType>::size(Self::LAYOUT),
+            }
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl #krate::Uniform for #ident {
+            const LAYOUT: #krate::BlockLayout = #krate::BlockLayout::#layout;
+
+            fn members() -> ::std::vec::Vec<#krate::UniformMember> {
+                // Every member is a `GlslType`, which is plain data, so an
+                // all-zero value is valid.
+                let value: Self = unsafe { ::std::mem::zeroed() };
+                let base = &value as *const Self as usize;
+                vec![#(#members),*]
+            }
+        }
+
+        unsafe impl #krate::GlslType for #ident {
+            fn align(layout: #krate::BlockLayout) -> u32 {
+                #krate::struct_align(&<Self as #krate::Uniform>::members(), layout)
+            }
+
+            fn size(layout: #krate::BlockLayout) -> u32 {
+                #krate::struct_size(&<Self as #krate::Uniform>::members(), layout)
+            }
+        }
+    })
+}
+
+/// Reads `#[uniform(std140)]` or `#[uniform(std430)]`, defaulting to `std140`.
+fn block_layout(attrs: &[Attribute]) -> Result<Ident> {
+    let mut layout = format_ident!("Std140");
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("uniform")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "Expected #[uniform(std140)] or #[uniform(std430)]",
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("std140") => {
+                    layout = format_ident!("Std140")
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("std430") => {
+                    layout = format_ident!("Std430")
+                }
+                nested => return Err(Error::new(nested.span(), "Expected `std140` or `std430`")),
+            }
+        }
+    }
+
+    Ok(layout)
+}
+
+fn has_repr_c(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("C"),
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    name: Option<String>,
+    format: Option<Ident>,
+}
+
+impl FieldOptions {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = FieldOptions::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("vertex")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => {
+                    return Err(Error::new(
+                        meta.span(),
+                        "Expected #[vertex(name = \"...\", format = \"...\")]",
+                    ))
+                }
+            };
+
+            for nested in list.nested {
+                let pair = match nested {
+                    NestedMeta::Meta(Meta::NameValue(pair)) => pair,
+                    nested => return Err(Error::new(nested.span(), "Expected `key = \"value\"`")),
+                };
+
+                let value = match &pair.lit {
+                    Lit::Str(value) => value,
+                    lit => return Err(Error::new(lit.span(), "Expected a string literal")),
+                };
+
+                if pair.path.is_ident("name") {
+                    options.name = Some(value.value());
+                } else if pair.path.is_ident("format") {
+                    options.format = Some(value.parse()?);
+                } else {
+                    return Err(Error::new(
+                        pair.path.span(),
+                        format!(
+                            "Unknown vertex option `{}`, expected `name` or `format`",
+                            pair.path.to_token_stream()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Maps `f32`, `u32` and `i32` and arrays of up to four of them to a format.
+fn infer_format(ty: &Type) -> Option<Ident> {
+    let (scalar, count) = match ty {
+        Type::Path(path) => (path.path.get_ident()?.to_string(), 1),
+        Type::Array(array) => {
+            let scalar = match &*array.elem {
+                Type::Path(path) => path.path.get_ident()?.to_string(),
+                _ => return None,
+            };
+            let count = match &array.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: Lit::Int(int), ..
+                }) => int.base10_parse::<usize>().ok()?,
+                _ => return None,
+            };
+            (scalar, count)
+        }
+        _ => return None,
+    };
+
+    let components = match count {
+        1 => "R",
+        2 => "Rg",
+        3 => "Rgb",
+        4 => "Rgba",
+        _ => return None,
+    };
+
+    let suffix = match scalar.as_str() {
+        "f32" => "32Sfloat",
+        "u32" => "32Uint",
+        "i32" => "32Sint",
+        _ => return None,
+    };
+
+    Some(format_ident!("{}{}", components, suffix, span = ty.span()))
+}
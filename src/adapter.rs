@@ -0,0 +1,119 @@
+//! Listing the adapters rendy can create a device on, and picking one of them.
+
+use std::fmt;
+
+use rendy::hal::{
+    self,
+    adapter::{Adapter, AdapterInfo, PhysicalDevice as _},
+    Instance as _,
+};
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+
+/// Selects an adapter by its index in the adapter list, or by a substring of
+/// its name, ignoring case.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum AdapterSelector {
+    Index(usize),
+    Name(String),
+}
+
+impl AdapterSelector {
+    /// The index of the first adapter in `adapters` this selects.
+    pub fn find(&self, adapters: &[AdapterInfo]) -> Option<usize> {
+        match self {
+            AdapterSelector::Index(index) => Some(*index).filter(|index| *index < adapters.len()),
+            AdapterSelector::Name(name) => {
+                let name = name.to_lowercase();
+                adapters
+                    .iter()
+                    .position(|adapter| adapter.name.to_lowercase().contains(&name))
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a str> for AdapterSelector {
+    /// Numbers select by index, anything else by name.
+    fn from(selector: &'a str) -> Self {
+        selector
+            .parse()
+            .map(AdapterSelector::Index)
+            .unwrap_or_else(|_| AdapterSelector::Name(selector.into()))
+    }
+}
+
+impl fmt::Display for AdapterSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdapterSelector::Index(index) => write!(f, "adapter {}", index),
+            AdapterSelector::Name(name) => write!(f, "adapter matching `{}`", name),
+        }
+    }
+}
+
+/// What `--list-adapters` prints about an adapter.
+#[derive(Clone, Debug)]
+pub struct AdapterSummary {
+    pub index: usize,
+    pub info: AdapterInfo,
+    pub features: hal::Features,
+    pub limits: hal::Limits,
+}
+
+impl AdapterSummary {
+    pub fn new<B: hal::Backend>(index: usize, adapter: &Adapter<B>) -> Self {
+        AdapterSummary {
+            index,
+            info: adapter.info.clone(),
+            features: adapter.physical_device.features(),
+            limits: adapter.physical_device.limits(),
+        }
+    }
+}
+
+impl fmt::Display for AdapterSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} ({:?}, vendor {:#06x}, device {:#06x})",
+            self.index, self.info.name, self.info.device_type, self.info.vendor, self.info.device
+        )?;
+        writeln!(
+            f,
+            "    max image size {}, max descriptor sets {}, max push constants {} bytes",
+            self.limits.max_image_2d_size,
+            self.limits.max_bound_descriptor_sets,
+            self.limits.max_push_constants_size
+        )?;
+        write!(f, "    features: {:?}", self.features)
+    }
+}
+
+#[cfg(not(any(feature = "vulkan", feature = "empty")))]
+compile_error!("Enable a backend, either the `vulkan` or the `empty` feature");
+
+/// The backend `init_auto` ends up with: Vulkan when it is enabled, the
+/// empty backend otherwise.
+#[cfg(feature = "vulkan")]
+type AutoBackend = rendy::vulkan::Backend;
+#[cfg(all(feature = "empty", not(feature = "vulkan")))]
+type AutoBackend = rendy::empty::Backend;
+
+type AutoInstance = <AutoBackend as hal::Backend>::Instance;
+
+/// The adapters of the backend rendy initializes with, in the order rendy
+/// offers them when picking one. Only an instance is created, no device.
+pub fn list() -> Result<Vec<AdapterSummary>> {
+    let instance = AutoInstance::create("glium tutorial", 1)
+        .map_err(|err| Error::Init(format!("{:?}", err)))?;
+
+    Ok(instance
+        .enumerate_adapters()
+        .iter()
+        .enumerate()
+        .map(|(index, adapter)| AdapterSummary::new(index, adapter))
+        .collect())
+}
@@ -0,0 +1,539 @@
+use std::{str::FromStr, time::Instant};
+
+use rendy::{
+    command::Families,
+    factory::Factory,
+    graph::{present::PresentNode, render::SubpassBuilder, Graph, GraphBuilder},
+    hal::{self, window::Extent2D},
+    init::AnyWindowedRendy,
+    wsi::Surface,
+};
+use winit::{
+    dpi::LogicalSize,
+    event::{DeviceEvent, Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::{Window, WindowBuilder},
+};
+
+use crate::{
+    config::PresentConfig,
+    error::Error,
+    input::{Input, KeyBindings},
+    shader::{self, ShaderSource, ShaderWatcher},
+    Camera, Clock, FrameContext, RendyConfig,
+};
+
+/// A single tutorial step.
+///
+/// The harness owns the window, the factory, the camera and the render graph.
+/// A chapter only supplies the subpass it wants to render and advances its
+/// own part of the `FrameContext` once per frame.
+pub trait Chapter: 'static {
+    /// The chapter's own part of the `FrameContext` threaded through
+    /// `GraphBuilder::build` and `Graph::run`.
+    type Aux: 'static;
+
+    /// What `compile` produces and `subpass_with` builds the pipeline from,
+    /// `Shaders` for every chapter that draws something.
+    type Compiled: Clone + 'static;
+
+    /// The aux value the graph is built with.
+    fn aux(&self) -> Self::Aux;
+
+    /// Compiles the chapter's shaders and checks its types against them.
+    fn compile(&self) -> Result<Self::Compiled, Error>;
+
+    /// The subpass rendering this chapter with `compiled`. The harness
+    /// attaches the color target.
+    fn subpass_with<B: hal::Backend>(
+        &self,
+        compiled: Self::Compiled,
+    ) -> Result<SubpassBuilder<B, FrameContext<Self::Aux>>, Error>;
+
+    /// The subpass rendering this chapter, compiled from its current shaders.
+    fn subpass<B: hal::Backend>(
+        &self,
+    ) -> Result<SubpassBuilder<B, FrameContext<Self::Aux>>, Error> {
+        self.subpass_with(self.compile()?)
+    }
+
+    /// The shader sources the subpass is compiled from, watched for changes in
+    /// hot reload mode.
+    fn shaders(&self) -> &'static [ShaderSource] {
+        &[]
+    }
+
+    /// Called once per frame, after `ctx` has moved on to the new frame and
+    /// before the graph is run.
+    fn update(&mut self, _ctx: &mut FrameContext<Self::Aux>) {}
+
+    /// Whether the chapter changes from frame to frame on its own. Animated
+    /// chapters are redrawn continuously by default.
+    fn is_animated(&self) -> bool {
+        false
+    }
+}
+
+/// When the window is redrawn. Neither redraws a minimized window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Redraw {
+    /// Whenever the event loop runs out of events, keeping a CPU core busy.
+    Continuous,
+    /// Only after input, resizing, a shader reload or while an animation is
+    /// running, waiting for events in between.
+    OnDemand,
+}
+
+impl FromStr for Redraw {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match name {
+            "continuous" => Ok(Redraw::Continuous),
+            "on-demand" => Ok(Redraw::OnDemand),
+            _ => Err(format!("unknown redraw mode `{}`", name)),
+        }
+    }
+}
+
+pub const CLEAR_COLOR: hal::command::ClearValue = hal::command::ClearValue {
+    color: hal::command::ClearColor {
+        float32: [0.0, 0.0, 1.0, 1.0],
+    },
+};
+
+pub fn init_logger() {
+    env_logger::Builder::from_default_env()
+        .filter_module("glium_tutorial_but_its_rendy", log::LevelFilter::Trace)
+        .init();
+}
+
+pub struct App<C> {
+    chapter: C,
+    title: String,
+    size: Option<(u32, u32)>,
+    clock: Clock,
+    camera: Camera,
+    key_bindings: Option<KeyBindings>,
+    hot_reload: bool,
+    config: RendyConfig,
+    redraw: Option<Redraw>,
+}
+
+impl<C> App<C>
+where
+    C: Chapter,
+{
+    pub fn new(chapter: C) -> Self {
+        App {
+            chapter,
+            title: "glium tutorial but it's rendy".into(),
+            size: None,
+            clock: Clock::new(),
+            camera: std::env::var("TUTORIAL_CAMERA")
+                .ok()
+                .and_then(|name| Camera::from_name(&name))
+                .unwrap_or_default(),
+            key_bindings: None,
+            hot_reload: std::env::var_os("TUTORIAL_HOT_RELOAD").is_some(),
+            config: RendyConfig::default(),
+            redraw: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// The initial inner size of the window, in logical pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The camera looking at the chapter. Also picked by setting
+    /// `TUTORIAL_CAMERA` to `fixed`, `orbit` or `fly`.
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// The buttons triggering each action. Without this the bindings are
+    /// loaded from the file `TUTORIAL_KEY_BINDINGS` points at, if it is set.
+    pub fn with_key_bindings(mut self, key_bindings: KeyBindings) -> Self {
+        self.key_bindings = Some(key_bindings);
+        self
+    }
+
+    /// Watches the chapter's shader sources and rebuilds the graph when they
+    /// change. Also enabled by setting `TUTORIAL_HOT_RELOAD`.
+    pub fn with_hot_reload(mut self, hot_reload: bool) -> Self {
+        self.hot_reload = hot_reload;
+        self
+    }
+
+    /// How rendy picks the device and sets up its queues and memory.
+    pub fn with_config(mut self, config: RendyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// When the window is redrawn. Defaults to `Redraw::Continuous` for
+    /// animated chapters and `Redraw::OnDemand` for the others.
+    pub fn with_redraw(mut self, redraw: Redraw) -> Self {
+        self.redraw = Some(redraw);
+        self
+    }
+
+    pub fn run(mut self) -> Result<(), Error> {
+        init_logger();
+
+        if self.key_bindings.is_none() {
+            if let Some(path) = std::env::var_os("TUTORIAL_KEY_BINDINGS") {
+                self.key_bindings = Some(KeyBindings::load(path)?);
+            }
+        }
+
+        let config = self.config.factory_config()?;
+        let event_loop = EventLoop::new();
+        let mut window = WindowBuilder::new().with_title(self.title.as_str());
+        if let Some((width, height)) = self.size {
+            window = window.with_inner_size(LogicalSize::new(width as f64, height as f64));
+        }
+
+        let rendy = AnyWindowedRendy::init_auto(&config, window, &event_loop)
+            .map_err(|err| Error::Init(format!("{:?}", err)))?;
+        rendy::with_any_windowed_rendy!((rendy)
+            (factory, families, surface, window) => {
+                run_windowed(self, factory, families, surface, window, event_loop)
+            }
+        )
+    }
+}
+
+fn run_windowed<B, C>(
+    app: App<C>,
+    mut factory: Factory<B>,
+    mut families: Families<B>,
+    surface: Surface<B>,
+    window: Window,
+    event_loop: EventLoop<()>,
+) -> Result<(), Error>
+where
+    B: hal::Backend,
+    C: Chapter,
+{
+    let App {
+        mut chapter,
+        mut clock,
+        mut camera,
+        key_bindings,
+        hot_reload,
+        config,
+        redraw,
+        ..
+    } = app;
+
+    let redraw = redraw.unwrap_or(if chapter.is_animated() {
+        Redraw::Continuous
+    } else {
+        Redraw::OnDemand
+    });
+    log::debug!("Redraw mode: {:?}", redraw);
+
+    let mut cursor_grabbed = false;
+
+    let mut watcher = if shader::set_load_from_disk(hot_reload) {
+        log::info!("Watching shader sources for changes");
+        Some(ShaderWatcher::new(chapter.shaders()))
+    } else {
+        None
+    };
+
+    let mut aux = FrameContext::new(chapter.aux(), Input::new(key_bindings.unwrap_or_default()));
+
+    // The last shaders that built a pipeline. Rebuilds that aren't reloads
+    // reuse them instead of compiling the sources again.
+    let mut compiled = chapter.compile()?;
+
+    let format = factory.get_surface_format(&surface);
+    let mut surface = Some(surface);
+    let mut graph = None;
+    if let Some(extent) = extent(&window) {
+        graph = Some(build_graph(
+            chapter.subpass_with(compiled.clone())?,
+            &mut factory,
+            &mut families,
+            &mut surface,
+            &window,
+            extent,
+            &config.present,
+            &aux,
+        )?);
+    }
+    let mut rebuild = graph.is_none();
+    let mut dirty = true;
+    let mut idle = false;
+
+    event_loop.run(move |event, _, control_flow| {
+        aux.input.handle_event(&event);
+
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                window_id,
+            } if window_id == window.id() => *control_flow = ControlFlow::Exit,
+            Event::WindowEvent {
+                event: WindowEvent::Resized(_),
+                window_id,
+            }
+            | Event::WindowEvent {
+                event: WindowEvent::HiDpiFactorChanged(_),
+                window_id,
+            } if window_id == window.id() => rebuild = true,
+            Event::WindowEvent { window_id, .. } if window_id == window.id() => dirty = true,
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { .. },
+                ..
+            } if cursor_grabbed => dirty = true,
+            Event::EventsCleared => {
+                factory.maintain(&mut families);
+
+                if watcher.as_mut().map_or(false, ShaderWatcher::poll) {
+                    log::info!("Shader sources changed, reloading");
+                    // The window's swapchain can't be shared with a second
+                    // graph, so the new pipeline is built offscreen before the
+                    // running graph is torn down. A broken shader or pipeline
+                    // leaves the previous one in place.
+                    let reloaded = chapter.compile().and_then(|new| {
+                        probe_subpass(
+                            chapter.subpass_with(new.clone())?,
+                            &mut factory,
+                            &mut families,
+                            format,
+                            extent(&window).unwrap_or(aux.extent),
+                            &aux,
+                        )?;
+                        Ok(new)
+                    });
+                    match reloaded {
+                        Ok(new) => {
+                            compiled = new;
+                            rebuild = true;
+                        }
+                        Err(err) => log::error!("Keeping the previous pipeline: {:?}", err),
+                    }
+                }
+
+                // There is nothing to draw to while minimized, in either
+                // mode, and restoring the window sends a resize.
+                let unchanged = redraw == Redraw::OnDemand && !(dirty || rebuild);
+                if unchanged || extent(&window).is_none() {
+                    if *control_flow != ControlFlow::Exit {
+                        *control_flow = if watcher.is_some() {
+                            // Wakes up to poll the shader sources.
+                            ControlFlow::WaitUntil(Instant::now() + ShaderWatcher::INTERVAL)
+                        } else {
+                            ControlFlow::Wait
+                        };
+                    }
+                    idle = true;
+                    return;
+                }
+
+                // The time spent waiting for events is not part of any frame.
+                if idle {
+                    clock.skip_gap();
+                    idle = false;
+                }
+                clock.tick();
+
+                if rebuild {
+                    if let Some(graph) = graph.take() {
+                        graph.dispose(&mut factory, &aux);
+                    }
+
+                    // A minimized window has no extent to render to. Keep the
+                    // graph torn down until it is restored.
+                    if let Some(extent) = extent(&window) {
+                        let built = chapter.subpass_with(compiled.clone()).and_then(|subpass| {
+                            build_graph(
+                                subpass,
+                                &mut factory,
+                                &mut families,
+                                &mut surface,
+                                &window,
+                                extent,
+                                &config.present,
+                                &aux,
+                            )
+                        });
+
+                        // These shaders built a pipeline before, so this only
+                        // fails if the window can't be rendered to right now.
+                        // The next resize or reload tries again.
+                        match built {
+                            Ok(new) => graph = Some(new),
+                            Err(err) => log::error!("Unable to rebuild the graph: {:?}", err),
+                        }
+                        rebuild = false;
+                    }
+                }
+
+                let extent = extent(&window).unwrap_or(aux.extent);
+                aux.tick(&clock, extent);
+
+                camera.update(&aux.input, clock.wall_delta());
+                aux.camera = camera.projection(extent);
+                if camera.grabs_cursor() != cursor_grabbed {
+                    cursor_grabbed = camera.grabs_cursor();
+                    grab_cursor(&window, cursor_grabbed);
+                }
+
+                chapter.update(&mut aux);
+
+                if let Some(graph) = &mut graph {
+                    graph.run(&mut factory, &mut families, &aux);
+                }
+
+                // Held buttons keep moving the camera without sending events,
+                // and a running animation changes every frame.
+                dirty = aux.input.is_any_down() || (chapter.is_animated() && !clock.is_paused());
+                aux.input.end_frame();
+
+                if *control_flow != ControlFlow::Exit {
+                    *control_flow = ControlFlow::Poll;
+                }
+            }
+            Event::LoopDestroyed => {
+                if let Some(graph) = graph.take() {
+                    graph.dispose(&mut factory, &aux);
+                }
+            }
+            _ => {}
+        }
+    });
+}
+
+/// The framebuffer size of the window, or `None` while it is minimized.
+fn extent(window: &Window) -> Option<Extent2D> {
+    let size = window.inner_size().to_physical(window.hidpi_factor());
+    let (width, height) = (size.width as u32, size.height as u32);
+
+    if width == 0 || height == 0 {
+        None
+    } else {
+        Some(Extent2D { width, height })
+    }
+}
+
+/// Hides the cursor and keeps it in the window while `grab` is set.
+fn grab_cursor(window: &Window, grab: bool) {
+    if let Err(err) = window.set_cursor_grab(grab) {
+        log::warn!("Unable to grab the cursor: {:?}", err);
+    }
+    window.set_cursor_visible(!grab);
+}
+
+/// Builds `subpass` into an offscreen graph and disposes it again, to find out
+/// whether its pipeline builds without touching the window's swapchain.
+fn probe_subpass<B, T>(
+    subpass: SubpassBuilder<B, T>,
+    factory: &mut Factory<B>,
+    families: &mut Families<B>,
+    format: hal::format::Format,
+    extent: Extent2D,
+    aux: &T,
+) -> Result<(), Error>
+where
+    B: hal::Backend,
+    T: 'static,
+{
+    let mut graph_builder = GraphBuilder::<B, T>::new();
+
+    let color = graph_builder.create_image(
+        hal::image::Kind::D2(extent.width, extent.height, 1, 1),
+        1,
+        format,
+        Some(CLEAR_COLOR),
+    );
+    graph_builder.add_node(subpass.with_color(color).into_pass());
+
+    let graph = graph_builder
+        .build(factory, families, aux)
+        .map_err(|err| Error::Graph(format!("{:?}", err)))?;
+    graph.dispose(factory, aux);
+    Ok(())
+}
+
+/// Builds a graph rendering `subpass` and presenting it to the window.
+#[allow(clippy::too_many_arguments)]
+fn build_graph<B, T>(
+    subpass: SubpassBuilder<B, T>,
+    factory: &mut Factory<B>,
+    families: &mut Families<B>,
+    surface: &mut Option<Surface<B>>,
+    window: &Window,
+    extent: Extent2D,
+    present: &PresentConfig,
+    aux: &T,
+) -> Result<Graph<B, T>, Error>
+where
+    B: hal::Backend,
+    T: 'static,
+{
+    let surface = match surface.take() {
+        Some(surface) => surface,
+        None => factory
+            .create_surface(window)
+            .map_err(Error::resource("surface"))?,
+    };
+
+    log::debug!("Building graph for {}x{}", extent.width, extent.height);
+
+    let mut graph_builder = GraphBuilder::<B, T>::new();
+
+    let color = graph_builder.create_image(
+        hal::image::Kind::D2(extent.width, extent.height, 1, 1),
+        1,
+        factory.get_surface_format(&surface),
+        Some(CLEAR_COLOR),
+    );
+
+    let pass = graph_builder.add_node(subpass.with_color(color).into_pass());
+
+    let caps = factory.get_surface_capabilities(&surface);
+    let present_mode = present.choose_mode(caps.present_modes);
+    let image_count = present.choose_image_count(caps.image_count);
+    log::info!(
+        "Presenting with {:?} and {} swapchain images",
+        present_mode,
+        image_count
+    );
+
+    // Rules out every mode but the chosen one.
+    let priority = move |mode| {
+        if mode == present_mode {
+            Some(0)
+        } else {
+            None
+        }
+    };
+
+    graph_builder.add_node(
+        PresentNode::builder(factory, surface, color)
+            .with_present_modes_priority(priority)
+            .with_image_count(image_count)
+            .with_dependency(pass),
+    );
+
+    graph_builder
+        .build(factory, families, aux)
+        .map_err(|err| Error::Graph(format!("{:?}", err)))
+}
@@ -0,0 +1,16 @@
+use glium_tutorial_but_its_rendy::{
+    cli::{self, Options, USAGE},
+    Error,
+};
+
+fn main() -> Result<(), Error> {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    cli::run(&options)
+}
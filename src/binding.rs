@@ -0,0 +1,484 @@
+use std::ops::Range;
+
+use rendy::{
+    factory::Factory,
+    graph::render::{Layout, SetLayout},
+    hal::{
+        self,
+        device::Device as _,
+        pso::{DescriptorType, ShaderStageFlags},
+    },
+    resource::DescriptorSet,
+};
+use spirv_reflect::types::{ReflectBlockVariable, ReflectDescriptorType};
+
+use crate::{
+    error::{Error, Result},
+    uniform::{Uniform, UniformBuffer},
+};
+
+/// A member of a uniform or storage block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockMember {
+    pub name: String,
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// The layout of a uniform or storage block, as reflected from SPIR-V.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    /// The block's type name, `Locals` in `uniform Locals { ... }`.
+    pub name: String,
+    pub size: u32,
+    pub members: Vec<BlockMember>,
+}
+
+/// A descriptor declared by one or more shader stages.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DescriptorBinding {
+    pub set: u32,
+    pub binding: u32,
+    /// The GLSL variable name. Empty for blocks without an instance name.
+    pub name: String,
+    pub ty: DescriptorType,
+    pub count: usize,
+    pub stages: ShaderStageFlags,
+    pub block: Option<Block>,
+}
+
+impl DescriptorBinding {
+    /// Whether GLSL refers to this descriptor as `name`, either by its
+    /// variable name or, for blocks, by the block's type name.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name
+            || self
+                .block
+                .as_ref()
+                .map_or(false, |block| block.name == name)
+    }
+
+    fn display_name(&self) -> &str {
+        match &self.block {
+            Some(block) if self.name.is_empty() => &block.name,
+            _ => &self.name,
+        }
+    }
+}
+
+/// A push constant block declared by one or more shader stages.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PushConstantRange {
+    pub block: Block,
+    pub stages: ShaderStageFlags,
+    /// The bytes the block's members cover.
+    pub range: Range<u32>,
+}
+
+/// Every descriptor used by a set of shaders.
+///
+/// Unlike rendy's reflection this understands separate `texture2D` and
+/// `sampler` bindings, and keeps the names so resources can be bound by the
+/// name they have in GLSL.
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    bindings: Vec<DescriptorBinding>,
+    push_constants: Vec<PushConstantRange>,
+}
+
+impl Bindings {
+    /// Reflects the descriptors of SPIR-V modules running at the given stages.
+    /// Descriptors shared between stages must agree on their type.
+    pub fn reflect(modules: &[(&[u32], ShaderStageFlags)]) -> Result<Self> {
+        let mut bindings = Bindings::default();
+
+        for &(spirv, stage) in modules {
+            let module =
+                spirv_reflect::ShaderModule::load_u32_data(spirv).map_err(reflection_error)?;
+            let reflected = module
+                .enumerate_descriptor_bindings(None)
+                .map_err(reflection_error)?;
+
+            for reflected in reflected {
+                let ty = descriptor_type(reflected.descriptor_type).ok_or_else(|| {
+                    Error::Reflection(format!(
+                        "`{}` has an unsupported descriptor type {:?}",
+                        reflected.name, reflected.descriptor_type
+                    ))
+                })?;
+
+                let block = match ty {
+                    DescriptorType::UniformBuffer
+                    | DescriptorType::UniformBufferDynamic
+                    | DescriptorType::StorageBuffer
+                    | DescriptorType::StorageBufferDynamic => Some(block(&reflected.block)),
+                    _ => None,
+                };
+
+                bindings.insert(DescriptorBinding {
+                    set: reflected.set,
+                    binding: reflected.binding,
+                    name: reflected.name,
+                    ty,
+                    count: reflected.count as usize,
+                    stages: stage,
+                    block,
+                })?;
+            }
+
+            let blocks = module
+                .enumerate_push_constant_blocks(None)
+                .map_err(reflection_error)?;
+            for reflected in blocks {
+                let block = block(&reflected);
+                let start = block.members.iter().map(|m| m.offset).min().unwrap_or(0);
+                let end = block
+                    .members
+                    .iter()
+                    .map(|m| m.offset + m.size)
+                    .max()
+                    .unwrap_or(0);
+
+                bindings.insert_push_constants(PushConstantRange {
+                    block,
+                    stages: stage,
+                    range: start..end,
+                })?;
+            }
+        }
+
+        bindings
+            .bindings
+            .sort_by_key(|binding| (binding.set, binding.binding));
+        Ok(bindings)
+    }
+
+    fn insert(&mut self, new: DescriptorBinding) -> Result<()> {
+        let existing = self
+            .bindings
+            .iter_mut()
+            .find(|binding| binding.set == new.set && binding.binding == new.binding);
+
+        match existing {
+            Some(existing) if existing.ty != new.ty || existing.count != new.count => {
+                Err(Error::Reflection(format!(
+                    "set {} binding {} is `{}` ({:?}) in one stage and `{}` ({:?}) in another",
+                    new.set,
+                    new.binding,
+                    existing.display_name(),
+                    existing.ty,
+                    new.display_name(),
+                    new.ty,
+                )))
+            }
+            Some(existing) => {
+                existing.stages |= new.stages;
+                Ok(())
+            }
+            None => {
+                self.bindings.push(new);
+                Ok(())
+            }
+        }
+    }
+
+    fn insert_push_constants(&mut self, new: PushConstantRange) -> Result<()> {
+        let existing = self
+            .push_constants
+            .iter_mut()
+            .find(|range| range.block.name == new.block.name);
+
+        match existing {
+            Some(existing) if existing.block != new.block => Err(Error::Reflection(format!(
+                "push constant block `{}` is declared differently in two stages",
+                new.block.name,
+            ))),
+            Some(existing) => {
+                existing.stages |= new.stages;
+                Ok(())
+            }
+            None => {
+                self.push_constants.push(new);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DescriptorBinding> {
+        self.bindings.iter()
+    }
+
+    /// Looks a descriptor up by its GLSL name.
+    pub fn get(&self, name: &str) -> Result<&DescriptorBinding> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.is_named(name))
+            .ok_or_else(|| Error::Binding {
+                name: name.into(),
+                message: format!(
+                    "no such descriptor, the shaders declare {}",
+                    self.names().join(", ")
+                ),
+            })
+    }
+
+    /// Looks a push constant block up by its GLSL type name.
+    pub fn push_constants(&self, name: &str) -> Result<&PushConstantRange> {
+        self.push_constants
+            .iter()
+            .find(|range| range.block.name == name)
+            .ok_or_else(|| {
+                Error::PushConstants(format!(
+                    "the shaders declare no push constant block `{}`",
+                    name
+                ))
+            })
+    }
+
+    /// Checks that a resource for one of the `expected` descriptor types can
+    /// be bound as `name` in set `index`, where the descriptors at the
+    /// `written` binding numbers are already bound. Returns the binding
+    /// number to write to. `Binder` runs this for every resource.
+    pub fn check_bind(
+        &self,
+        index: u32,
+        name: &str,
+        expected: &[DescriptorType],
+        written: &[u32],
+    ) -> Result<u32> {
+        let binding = self.get(name)?;
+
+        if binding.set != index {
+            return Err(Error::Binding {
+                name: name.into(),
+                message: format!("declared in set {}, not in set {}", binding.set, index),
+            });
+        }
+
+        if !expected.contains(&binding.ty) {
+            return Err(Error::Binding {
+                name: name.into(),
+                message: format!(
+                    "the shaders declare a {:?}, got a resource for {:?}",
+                    binding.ty, expected
+                ),
+            });
+        }
+
+        if written.contains(&binding.binding) {
+            return Err(Error::Binding {
+                name: name.into(),
+                message: format!("already bound in set {}", index),
+            });
+        }
+
+        Ok(binding.binding)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.bindings
+            .iter()
+            .map(|binding| format!("`{}`", binding.display_name()))
+            .collect()
+    }
+
+    /// The pipeline layout, with one set layout per set index up to the
+    /// highest one in use.
+    pub fn layout(&self) -> Layout {
+        let sets = self
+            .bindings
+            .iter()
+            .map(|binding| binding.set + 1)
+            .max()
+            .unwrap_or(0);
+
+        Layout {
+            sets: (0..sets)
+                .map(|set| SetLayout {
+                    bindings: self
+                        .bindings
+                        .iter()
+                        .filter(|binding| binding.set == set)
+                        .map(|binding| hal::pso::DescriptorSetLayoutBinding {
+                            binding: binding.binding,
+                            ty: binding.ty,
+                            count: binding.count,
+                            stage_flags: binding.stages,
+                            immutable_samplers: false,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            push_constants: self
+                .push_constants
+                .iter()
+                .map(|range| (range.stages, range.range.clone()))
+                .collect(),
+        }
+    }
+
+    /// Starts writing resources into `set`, the descriptor set allocated for
+    /// set index `index`.
+    pub fn binder<'a, B: hal::Backend>(
+        &'a self,
+        index: u32,
+        set: &'a DescriptorSet<B>,
+    ) -> Binder<'a, B> {
+        Binder {
+            bindings: self,
+            index,
+            set,
+            writes: Vec::new(),
+        }
+    }
+}
+
+/// Collects descriptor writes by GLSL name, checking each resource against
+/// the type the shaders declare.
+#[derive(Debug)]
+pub struct Binder<'a, B: hal::Backend> {
+    bindings: &'a Bindings,
+    index: u32,
+    set: &'a DescriptorSet<B>,
+    writes: Vec<(u32, hal::pso::Descriptor<'a, B>)>,
+}
+
+impl<'a, B: hal::Backend> Binder<'a, B> {
+    fn bind(
+        mut self,
+        name: &str,
+        expected: &[DescriptorType],
+        descriptor: hal::pso::Descriptor<'a, B>,
+    ) -> Result<Self> {
+        let written: Vec<_> = self.writes.iter().map(|(binding, _)| *binding).collect();
+        let binding = self
+            .bindings
+            .check_bind(self.index, name, expected, &written)?;
+
+        self.writes.push((binding, descriptor));
+        Ok(self)
+    }
+
+    pub fn buffer(self, name: &str, buffer: &'a B::Buffer, size: u64) -> Result<Self> {
+        self.bind(
+            name,
+            &[DescriptorType::UniformBuffer, DescriptorType::StorageBuffer],
+            hal::pso::Descriptor::Buffer(buffer, None..Some(size)),
+        )
+    }
+
+    pub fn uniform<U: Uniform>(self, name: &str, buffer: &'a UniformBuffer<B, U>) -> Result<Self> {
+        self.bind(
+            name,
+            &[DescriptorType::UniformBuffer],
+            hal::pso::Descriptor::Buffer(buffer.raw(), None..Some(buffer.size())),
+        )
+    }
+
+    pub fn image(self, name: &str, view: &'a B::ImageView) -> Result<Self> {
+        self.bind(
+            name,
+            &[DescriptorType::SampledImage],
+            hal::pso::Descriptor::Image(view, hal::image::Layout::ShaderReadOnlyOptimal),
+        )
+    }
+
+    pub fn sampler(self, name: &str, sampler: &'a B::Sampler) -> Result<Self> {
+        self.bind(
+            name,
+            &[DescriptorType::Sampler],
+            hal::pso::Descriptor::Sampler(sampler),
+        )
+    }
+
+    pub fn combined_image_sampler(
+        self,
+        name: &str,
+        view: &'a B::ImageView,
+        sampler: &'a B::Sampler,
+    ) -> Result<Self> {
+        self.bind(
+            name,
+            &[DescriptorType::CombinedImageSampler],
+            hal::pso::Descriptor::CombinedImageSampler(
+                view,
+                hal::image::Layout::ShaderReadOnlyOptimal,
+                sampler,
+            ),
+        )
+    }
+
+    /// Writes the collected descriptors. Every descriptor in the set must
+    /// have been bound.
+    pub fn write(self, factory: &Factory<B>) -> Result<()> {
+        let missing: Vec<_> = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.set == self.index)
+            .filter(|binding| !self.writes.iter().any(|(b, _)| *b == binding.binding))
+            .map(|binding| format!("`{}`", binding.display_name()))
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(Error::Binding {
+                name: missing.join(", "),
+                message: format!("never bound in set {}", self.index),
+            });
+        }
+
+        let set = self.set.raw();
+        unsafe {
+            factory
+                .device()
+                .write_descriptor_sets(self.writes.into_iter().map(|(binding, descriptor)| {
+                    hal::pso::DescriptorSetWrite {
+                        set,
+                        binding,
+                        array_offset: 0,
+                        descriptors: Some(descriptor),
+                    }
+                }));
+        }
+        Ok(())
+    }
+}
+
+fn block(variable: &ReflectBlockVariable) -> Block {
+    Block {
+        name: variable
+            .type_description
+            .as_ref()
+            .map_or_else(String::new, |ty| ty.type_name.clone()),
+        size: variable.size,
+        members: variable
+            .members
+            .iter()
+            .map(|member| BlockMember {
+                name: member.name.clone(),
+                offset: member.offset,
+                size: member.size,
+            })
+            .collect(),
+    }
+}
+
+fn descriptor_type(ty: ReflectDescriptorType) -> Option<DescriptorType> {
+    Some(match ty {
+        ReflectDescriptorType::Sampler => DescriptorType::Sampler,
+        ReflectDescriptorType::CombinedImageSampler => DescriptorType::CombinedImageSampler,
+        ReflectDescriptorType::SampledImage => DescriptorType::SampledImage,
+        ReflectDescriptorType::StorageImage => DescriptorType::StorageImage,
+        ReflectDescriptorType::UniformTexelBuffer => DescriptorType::UniformTexelBuffer,
+        ReflectDescriptorType::StorageTexelBuffer => DescriptorType::StorageTexelBuffer,
+        ReflectDescriptorType::UniformBuffer => DescriptorType::UniformBuffer,
+        ReflectDescriptorType::StorageBuffer => DescriptorType::StorageBuffer,
+        ReflectDescriptorType::UniformBufferDynamic => DescriptorType::UniformBufferDynamic,
+        ReflectDescriptorType::StorageBufferDynamic => DescriptorType::StorageBufferDynamic,
+        ReflectDescriptorType::InputAttachment => DescriptorType::InputAttachment,
+        _ => return None,
+    })
+}
+
+fn reflection_error<E: std::fmt::Debug>(err: E) -> Error {
+    Error::Reflection(format!("{:?}", err))
+}
@@ -0,0 +1,255 @@
+//! Cameras controlled with the mouse and keyboard.
+//!
+//! World space matches the coordinates the chapters place their geometry at:
+//! +Y points down the screen and the geometry faces a camera at -Z, so both
+//! controllers initially show a chapter the way the fixed camera does.
+
+use std::f32::consts::FRAC_PI_2;
+
+use rendy::hal::window::Extent2D;
+
+use crate::{
+    input::{action, Input},
+    math::{Mat4, Vec3},
+    projection::Projection,
+};
+
+/// World space up, see the module documentation.
+pub const UP: Vec3 = Vec3::new(0.0, -1.0, 0.0);
+
+/// Vertical field of view of the perspective cameras, in radians.
+pub const FOV_Y: f32 = std::f32::consts::FRAC_PI_3;
+pub const NEAR: f32 = 0.1;
+pub const FAR: f32 = 100.0;
+
+/// Radians turned per pixel the mouse moves.
+const SENSITIVITY: f32 = 0.005;
+
+/// How far a scroll wheel notch moves an orbit camera towards its target.
+const ZOOM_STEP: f32 = 0.9;
+
+/// Keeps the cameras from flipping over when looking straight up or down.
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+/// How the harness turns input into the view and projection matrices.
+#[derive(Clone, Debug)]
+pub enum Camera {
+    /// Shows clip space as is, keeping it square on screen. Ignores input.
+    Fixed,
+    Orbit(OrbitCamera),
+    Fly(FlyCamera),
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera::Fixed
+    }
+}
+
+impl Camera {
+    /// Looks up a camera by the name used on the command line and in
+    /// `TUTORIAL_CAMERA`: `fixed`, `orbit` or `fly`.
+    pub fn from_name(name: &str) -> Option<Camera> {
+        match name {
+            "fixed" => Some(Camera::Fixed),
+            "orbit" => Some(Camera::Orbit(OrbitCamera::default())),
+            "fly" => Some(Camera::Fly(FlyCamera::default())),
+            _ => None,
+        }
+    }
+
+    /// Applies a frame's worth of `input`, with `delta` seconds passing.
+    pub fn update(&mut self, input: &Input, delta: f32) {
+        match self {
+            Camera::Fixed => {}
+            Camera::Orbit(orbit) => orbit.update(input),
+            Camera::Fly(fly) => fly.update(input, delta),
+        }
+    }
+
+    /// Whether the cursor should be hidden and kept in the window.
+    pub fn grabs_cursor(&self) -> bool {
+        match self {
+            Camera::Fly(fly) => fly.grabbed,
+            _ => false,
+        }
+    }
+
+    pub fn view(&self) -> Mat4 {
+        match self {
+            Camera::Fixed => Mat4::IDENTITY,
+            Camera::Orbit(orbit) => orbit.view(),
+            Camera::Fly(fly) => fly.view(),
+        }
+    }
+
+    /// The matrices for a framebuffer of the given size.
+    pub fn projection(&self, extent: Extent2D) -> Projection {
+        match self {
+            Camera::Fixed => Projection::for_extent(extent),
+            _ => {
+                let aspect = extent.width.max(1) as f32 / extent.height.max(1) as f32;
+                Projection {
+                    projection: Mat4::perspective(FOV_Y, aspect, NEAR, FAR),
+                    view: self.view(),
+                }
+            }
+        }
+    }
+}
+
+/// Circles a target. Dragging with the `look` button held rotates around it
+/// and scrolling zooms in and out.
+#[derive(Clone, Debug)]
+pub struct OrbitCamera {
+    pub target: Vec3,
+    pub distance: f32,
+    /// Rotation around the up axis, in radians.
+    pub yaw: f32,
+    /// Rotation above the target's horizon, in radians.
+    pub pitch: f32,
+}
+
+impl OrbitCamera {
+    pub fn new(target: Vec3, distance: f32) -> Self {
+        OrbitCamera {
+            target,
+            distance,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+
+    pub fn eye(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let offset = Vec3::new(-sin_yaw * cos_pitch, 0.0, -cos_yaw * cos_pitch) + UP * sin_pitch;
+        self.target + offset * self.distance
+    }
+
+    pub fn view(&self) -> Mat4 {
+        Mat4::look_at(self.eye(), self.target, UP)
+    }
+
+    /// Rotates around the target by `yaw` and `pitch` radians.
+    pub fn rotate(&mut self, yaw: f32, pitch: f32) {
+        self.yaw += yaw;
+        self.pitch = (self.pitch + pitch).max(-MAX_PITCH).min(MAX_PITCH);
+    }
+
+    /// Moves towards the target by `steps` scroll wheel notches, or away
+    /// from it if `steps` is negative.
+    pub fn zoom(&mut self, steps: f32) {
+        self.distance = (self.distance * ZOOM_STEP.powf(steps))
+            .max(NEAR)
+            .min(FAR / 2.0);
+    }
+
+    fn update(&mut self, input: &Input) {
+        if input.is_action_down(action::LOOK) {
+            let (dx, dy) = input.cursor_delta();
+            self.rotate(dx * SENSITIVITY, dy * SENSITIVITY);
+        }
+        self.zoom(input.scroll());
+    }
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        OrbitCamera::new(Vec3::ZERO, 2.0)
+    }
+}
+
+/// Flies freely. Pressing `look` grabs the cursor to look around and
+/// `release` lets go of it again. The `forward`, `back`, `left`, `right`, `up`
+/// and `down` actions move the camera.
+#[derive(Clone, Debug)]
+pub struct FlyCamera {
+    pub position: Vec3,
+    /// Rotation around the up axis, in radians. Zero looks down +Z.
+    pub yaw: f32,
+    /// Rotation above the horizon, in radians.
+    pub pitch: f32,
+    /// Units moved per second.
+    pub speed: f32,
+    grabbed: bool,
+}
+
+impl FlyCamera {
+    pub fn new(position: Vec3) -> Self {
+        FlyCamera {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            speed: 1.0,
+            grabbed: false,
+        }
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        Vec3::new(sin_yaw * cos_pitch, 0.0, cos_yaw * cos_pitch) + UP * sin_pitch
+    }
+
+    pub fn right(&self) -> Vec3 {
+        self.forward().cross(UP).normalize()
+    }
+
+    pub fn view(&self) -> Mat4 {
+        Mat4::look_at(self.position, self.position + self.forward(), UP)
+    }
+
+    /// Turns by `yaw` and `pitch` radians.
+    pub fn look(&mut self, yaw: f32, pitch: f32) {
+        self.yaw += yaw;
+        self.pitch = (self.pitch + pitch).max(-MAX_PITCH).min(MAX_PITCH);
+    }
+
+    /// Moves by `distance` along the camera's own forward, right and up axes.
+    pub fn fly(&mut self, distance: Vec3) {
+        self.position = self.position
+            + self.right() * distance.x
+            + UP * distance.y
+            + self.forward() * distance.z;
+    }
+
+    fn update(&mut self, input: &Input, delta: f32) {
+        if input.action_just_pressed(action::LOOK) {
+            self.grabbed = true;
+        }
+        if input.action_just_pressed(action::RELEASE) || !input.is_focused() {
+            self.grabbed = false;
+        }
+
+        if self.grabbed {
+            // Raw motion keeps being reported while the cursor is held in place.
+            let (dx, dy) = input.motion();
+            self.look(dx * SENSITIVITY, -dy * SENSITIVITY);
+        }
+
+        let axis = |positive, negative| match (
+            input.is_action_down(positive),
+            input.is_action_down(negative),
+        ) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        let direction = Vec3::new(
+            axis(action::RIGHT, action::LEFT),
+            axis(action::UP, action::DOWN),
+            axis(action::FORWARD, action::BACK),
+        );
+
+        if direction != Vec3::ZERO {
+            self.fly(direction.normalize() * self.speed * delta);
+        }
+    }
+}
+
+impl Default for FlyCamera {
+    fn default() -> Self {
+        FlyCamera::new(Vec3::new(0.0, 0.0, -2.0))
+    }
+}
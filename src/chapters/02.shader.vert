@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec2 a_Pos;
+
+layout(set = 1, binding = 0) uniform Projection {
+    mat4 projection;
+    mat4 view;
+};
+
+void main() {
+    gl_Position = projection * view * vec4(a_Pos, 0.0, 1.0);
+}
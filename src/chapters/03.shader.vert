@@ -6,8 +6,13 @@ layout(set = 0, binding = 0) uniform Locals {
     float t;
 };
 
+layout(set = 1, binding = 0) uniform Projection {
+    mat4 projection;
+    mat4 view;
+};
+
 void main() {
     vec2 pos = a_Pos;
     pos.x += t;
-    gl_Position = vec4(pos, 0.0, 1.0);
+    gl_Position = projection * view * vec4(pos, 0.0, 1.0);
 }
@@ -7,7 +7,12 @@ layout(set = 0, binding = 0) uniform Locals {
     mat4 matrix;
 };
 
+layout(set = 1, binding = 0) uniform Projection {
+    mat4 projection;
+    mat4 view;
+};
+
 void main() {
     my_attr = a_Pos;
-    gl_Position = matrix * vec4(a_Pos, 0.0, 1.0);
+    gl_Position = projection * view * matrix * vec4(a_Pos, 0.0, 1.0);
 }
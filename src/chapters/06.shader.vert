@@ -4,11 +4,16 @@ layout(location = 0) in vec2 a_Pos;
 layout(location = 1) in vec2 tex_coords;
 layout(location = 0) out vec2 tex_coords_out;
 
-layout(set = 0, binding = 0) uniform _ {
+layout(set = 0, binding = 0) uniform Locals {
     mat4 matrix;
 };
 
+layout(set = 1, binding = 0) uniform Projection {
+    mat4 projection;
+    mat4 view;
+};
+
 void main() {
     tex_coords_out = tex_coords;
-    gl_Position = matrix * vec4(a_Pos, 0.0, 1.0);
+    gl_Position = projection * view * matrix * vec4(a_Pos, 0.0, 1.0);
 }
@@ -0,0 +1,35 @@
+use rendy::{graph::render::SubpassBuilder, hal};
+
+use crate::{
+    cli::{launch, Entry},
+    error::Error,
+    Chapter, FrameContext,
+};
+
+/// Clears the window to blue without drawing anything.
+#[derive(Debug, Default)]
+pub struct Chapter01;
+
+pub const ENTRY: Entry = Entry {
+    name: "01",
+    description: "Clears the window to blue without drawing anything.",
+    launch: launch::<Chapter01>,
+};
+
+impl Chapter for Chapter01 {
+    type Aux = ();
+    type Compiled = ();
+
+    fn aux(&self) {}
+
+    fn compile(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn subpass_with<B: hal::Backend>(
+        &self,
+        _: (),
+    ) -> Result<SubpassBuilder<B, FrameContext<()>>, Error> {
+        Ok(SubpassBuilder::new())
+    }
+}
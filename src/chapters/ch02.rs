@@ -0,0 +1,201 @@
+use rendy::{
+    command::{QueueId, RenderPassEncoder},
+    factory::Factory,
+    graph::{render::*, GraphContext, NodeBuffer, NodeImage},
+    hal,
+    memory::Dynamic,
+    resource::{Buffer, BufferInfo, DescriptorSetLayout, Escape, Handle},
+};
+
+use super::shaders::{FRAGMENT_02 as FRAGMENT, VERTEX_02 as VERTEX};
+use crate::{
+    cli::{launch, Entry},
+    error::{creation_error, prepare_error, Error},
+    projection::{Projection, ProjectionSet},
+    shader::{ShaderSource, Shaders},
+    Chapter, FrameContext, Vertex,
+};
+
+/// Draws a single static triangle.
+#[derive(Debug, Default)]
+pub struct Chapter02;
+
+pub const ENTRY: Entry = Entry {
+    name: "02",
+    description: "Draws a single static triangle.",
+    launch: launch::<Chapter02>,
+};
+
+impl Chapter for Chapter02 {
+    type Aux = ();
+    type Compiled = Shaders;
+
+    fn aux(&self) {}
+
+    fn compile(&self) -> Result<Shaders, Error> {
+        let shaders = Shaders::compile(&VERTEX, &FRAGMENT)?;
+        shaders.check_vertex::<Vertex>()?;
+        Ok(shaders)
+    }
+
+    fn subpass_with<B: hal::Backend>(
+        &self,
+        shaders: Shaders,
+    ) -> Result<SubpassBuilder<B, FrameContext<()>>, Error> {
+        Ok(TutorialRenderPipelineDesc { shaders }
+            .builder()
+            .into_subpass())
+    }
+
+    fn shaders(&self) -> &'static [ShaderSource] {
+        &[VERTEX, FRAGMENT]
+    }
+}
+
+#[derive(Debug)]
+struct TutorialRenderPipelineDesc {
+    shaders: Shaders,
+}
+
+impl<B, T> SimpleGraphicsPipelineDesc<B, FrameContext<T>> for TutorialRenderPipelineDesc
+where
+    B: hal::Backend,
+{
+    type Pipeline = TutorialRenderPipeline<B>;
+
+    fn depth_stencil(&self) -> Option<hal::pso::DepthStencilDesc> {
+        None
+    }
+
+    fn load_shader_set(
+        &self,
+        factory: &mut Factory<B>,
+        _aux: &FrameContext<T>,
+    ) -> rendy::shader::ShaderSet<B> {
+        self.shaders.build(factory)
+    }
+
+    fn vertices(
+        &self,
+    ) -> Vec<(
+        Vec<hal::pso::Element<hal::format::Format>>,
+        hal::pso::ElemStride,
+        hal::pso::VertexInputRate,
+    )> {
+        self.shaders.vertices()
+    }
+
+    fn layout(&self) -> Layout {
+        self.shaders.layout()
+    }
+
+    fn build<'a>(
+        self,
+        ctx: &GraphContext<B>,
+        factory: &mut Factory<B>,
+        _queue: QueueId,
+        _aux: &FrameContext<T>,
+        buffers: Vec<NodeBuffer>,
+        images: Vec<NodeImage>,
+        set_layouts: &[Handle<DescriptorSetLayout<B>>],
+    ) -> Result<TutorialRenderPipeline<B>, hal::pso::CreationError> {
+        assert!(buffers.is_empty());
+        assert!(images.is_empty());
+        assert_eq!(set_layouts.len(), 2);
+
+        let projection = ProjectionSet::new(
+            factory,
+            &self.shaders,
+            &set_layouts[Projection::SET as usize],
+            ctx.frames_in_flight as usize,
+        )
+        .map_err(creation_error("projection"))?;
+
+        let vbuf_size = self.shaders.vertex.stride as u64 * 3;
+
+        let mut vbuf = factory
+            .create_buffer(
+                BufferInfo {
+                    size: vbuf_size,
+                    usage: hal::buffer::Usage::VERTEX,
+                },
+                Dynamic,
+            )
+            .map_err(creation_error("vertex buffer"))?;
+
+        unsafe {
+            factory
+                .upload_visible_buffer(
+                    &mut vbuf,
+                    0,
+                    &[
+                        Vertex {
+                            position: [-0.5, 0.5],
+                        },
+                        Vertex {
+                            position: [0.0, -0.5],
+                        },
+                        Vertex {
+                            position: [0.5, 0.25],
+                        },
+                    ],
+                )
+                .map_err(creation_error("vertex data"))?;
+        }
+
+        Ok(TutorialRenderPipeline {
+            projection,
+            vertex: vbuf,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct TutorialRenderPipeline<B: hal::Backend> {
+    projection: ProjectionSet<B>,
+    vertex: Escape<Buffer<B>>,
+}
+
+impl<B, T> SimpleGraphicsPipeline<B, FrameContext<T>> for TutorialRenderPipeline<B>
+where
+    B: hal::Backend,
+{
+    type Desc = TutorialRenderPipelineDesc;
+
+    fn prepare(
+        &mut self,
+        factory: &Factory<B>,
+        _queue: QueueId,
+        _set_layouts: &[Handle<DescriptorSetLayout<B>>],
+        index: usize,
+        aux: &FrameContext<T>,
+    ) -> PrepareResult {
+        if let Err(err) = self.projection.write(factory, index, &aux.camera) {
+            return prepare_error(err);
+        }
+        PrepareResult::DrawReuse
+    }
+
+    fn draw(
+        &mut self,
+        layout: &B::PipelineLayout,
+        mut encoder: RenderPassEncoder<'_, B>,
+        index: usize,
+        _aux: &FrameContext<T>,
+    ) {
+        self.projection.bind(&mut encoder, layout, index);
+        unsafe {
+            encoder.bind_vertex_buffers(0, Some((self.vertex.raw(), 0)));
+            encoder.draw(0..3, 0..1);
+        }
+    }
+
+    fn dispose(self, _factory: &mut Factory<B>, _aux: &FrameContext<T>) {}
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct Vertex {
+    #[vertex(name = "a_Pos")]
+    position: [f32; 2],
+}
@@ -0,0 +1,252 @@
+use rendy::{
+    command::{QueueId, RenderPassEncoder},
+    factory::Factory,
+    graph::{render::*, GraphContext, NodeBuffer, NodeImage},
+    hal,
+    memory::Dynamic,
+    resource::{Buffer, BufferInfo, DescriptorSetLayout, Escape, Handle},
+};
+
+use super::shaders::{FRAGMENT_03 as FRAGMENT, VERTEX_03 as VERTEX};
+use crate::{
+    cli::{launch, Entry},
+    error::{creation_error, prepare_error, Error},
+    projection::{Projection, ProjectionSet},
+    shader::{ShaderSource, Shaders},
+    Chapter, FrameContext, FrameUniforms, Uniform, Vertex,
+};
+
+/// Slides the triangle horizontally using a uniform buffer.
+#[derive(Debug, Default)]
+pub struct Chapter03;
+
+pub const ENTRY: Entry = Entry {
+    name: "03",
+    description: "Slides the triangle horizontally using a uniform buffer.",
+    launch: launch::<Chapter03>,
+};
+
+impl Chapter for Chapter03 {
+    type Aux = f32;
+    type Compiled = Shaders;
+
+    fn aux(&self) -> f32 {
+        -0.5
+    }
+
+    fn compile(&self) -> Result<Shaders, Error> {
+        let shaders = Shaders::compile(&VERTEX, &FRAGMENT)?;
+        shaders.check_vertex::<Vertex>()?;
+        shaders.check_uniform::<UniformLocals>("Locals")?;
+        Ok(shaders)
+    }
+
+    fn subpass_with<B: hal::Backend>(
+        &self,
+        shaders: Shaders,
+    ) -> Result<SubpassBuilder<B, FrameContext<f32>>, Error> {
+        let layout = shaders.layout();
+        Ok(TutorialRenderPipelineDesc { shaders, layout }
+            .builder()
+            .into_subpass())
+    }
+
+    fn shaders(&self) -> &'static [ShaderSource] {
+        &[VERTEX, FRAGMENT]
+    }
+
+    fn update(&mut self, ctx: &mut FrameContext<f32>) {
+        super::animate_t(ctx);
+    }
+
+    fn is_animated(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug)]
+struct TutorialRenderPipelineDesc {
+    shaders: Shaders,
+    layout: Layout,
+}
+
+impl<B> SimpleGraphicsPipelineDesc<B, FrameContext<f32>> for TutorialRenderPipelineDesc
+where
+    B: hal::Backend,
+{
+    type Pipeline = TutorialRenderPipeline<B>;
+
+    fn depth_stencil(&self) -> Option<hal::pso::DepthStencilDesc> {
+        None
+    }
+
+    fn load_shader_set(
+        &self,
+        factory: &mut Factory<B>,
+        _aux: &FrameContext<f32>,
+    ) -> rendy::shader::ShaderSet<B> {
+        self.shaders.build(factory)
+    }
+
+    fn vertices(
+        &self,
+    ) -> Vec<(
+        Vec<hal::pso::Element<hal::format::Format>>,
+        hal::pso::ElemStride,
+        hal::pso::VertexInputRate,
+    )> {
+        self.shaders.vertices()
+    }
+
+    fn layout(&self) -> Layout {
+        self.layout.clone()
+    }
+
+    fn build<'a>(
+        self,
+        ctx: &GraphContext<B>,
+        factory: &mut Factory<B>,
+        _queue: QueueId,
+        _aux: &FrameContext<f32>,
+        buffers: Vec<NodeBuffer>,
+        images: Vec<NodeImage>,
+        set_layouts: &[Handle<DescriptorSetLayout<B>>],
+    ) -> Result<TutorialRenderPipeline<B>, hal::pso::CreationError> {
+        assert!(buffers.is_empty());
+        assert!(images.is_empty());
+        assert_eq!(set_layouts.len(), 2);
+
+        let projection = ProjectionSet::new(
+            factory,
+            &self.shaders,
+            &set_layouts[Projection::SET as usize],
+            ctx.frames_in_flight as usize,
+        )
+        .map_err(creation_error("projection"))?;
+
+        let uniforms = self
+            .shaders
+            .block("Locals")
+            .and_then(|block| {
+                FrameUniforms::new(
+                    factory,
+                    ctx.frames_in_flight as usize,
+                    block,
+                    &set_layouts[0],
+                )
+            })
+            .map_err(creation_error("uniform buffers"))?;
+
+        uniforms
+            .bind(factory, &self.shaders.bindings, 0, "Locals", Ok)
+            .map_err(creation_error("descriptor sets"))?;
+
+        let vbuf_size = self.shaders.vertex.stride as u64 * 3;
+
+        let mut vbuf = factory
+            .create_buffer(
+                BufferInfo {
+                    size: vbuf_size,
+                    usage: hal::buffer::Usage::VERTEX,
+                },
+                Dynamic,
+            )
+            .map_err(creation_error("vertex buffer"))?;
+
+        unsafe {
+            factory
+                .upload_visible_buffer(
+                    &mut vbuf,
+                    0,
+                    &[
+                        Vertex {
+                            position: [-0.5, 0.5],
+                        },
+                        Vertex {
+                            position: [0.0, -0.5],
+                        },
+                        Vertex {
+                            position: [0.5, 0.25],
+                        },
+                    ],
+                )
+                .map_err(creation_error("vertex data"))?;
+        }
+
+        Ok(TutorialRenderPipeline {
+            projection,
+            uniforms,
+            vertex: vbuf,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct TutorialRenderPipeline<B: hal::Backend> {
+    projection: ProjectionSet<B>,
+    uniforms: FrameUniforms<B, UniformLocals>,
+    vertex: Escape<Buffer<B>>,
+}
+
+impl<B> SimpleGraphicsPipeline<B, FrameContext<f32>> for TutorialRenderPipeline<B>
+where
+    B: hal::Backend,
+{
+    type Desc = TutorialRenderPipelineDesc;
+
+    fn prepare(
+        &mut self,
+        factory: &Factory<B>,
+        _queue: QueueId,
+        _set_layouts: &[Handle<DescriptorSetLayout<B>>],
+        index: usize,
+        aux: &FrameContext<f32>,
+    ) -> PrepareResult {
+        let written = self
+            .projection
+            .write(factory, index, &aux.camera)
+            .and_then(|()| {
+                self.uniforms
+                    .write(factory, index, &UniformLocals { t: aux.user })
+            });
+        if let Err(err) = written {
+            return prepare_error(err);
+        }
+        PrepareResult::DrawReuse
+    }
+
+    fn draw(
+        &mut self,
+        layout: &B::PipelineLayout,
+        mut encoder: RenderPassEncoder<'_, B>,
+        index: usize,
+        _aux: &FrameContext<f32>,
+    ) {
+        self.projection.bind(&mut encoder, layout, index);
+        unsafe {
+            encoder.bind_graphics_descriptor_sets(
+                layout,
+                0,
+                std::iter::once(self.uniforms.descriptor_set(index)),
+                std::iter::empty(),
+            );
+            encoder.bind_vertex_buffers(0, Some((self.vertex.raw(), 0)));
+            encoder.draw(0..3, 0..1);
+        }
+    }
+
+    fn dispose(self, _factory: &mut Factory<B>, _aux: &FrameContext<f32>) {}
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct Vertex {
+    #[vertex(name = "a_Pos")]
+    position: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct UniformLocals {
+    t: f32,
+}
@@ -0,0 +1,51 @@
+mod ch01;
+mod ch02;
+mod ch03;
+mod ch03x1;
+mod ch04;
+mod ch05;
+mod ch06;
+
+/// The chapter shaders, compiled to SPIR-V by the build script.
+pub mod shaders {
+    use rendy::hal::pso::ShaderStageFlags;
+
+    use crate::shader::ShaderSource;
+
+    include!(concat!(env!("OUT_DIR"), "/chapter_shaders.rs"));
+}
+
+pub use self::{
+    ch01::Chapter01, ch02::Chapter02, ch03::Chapter03, ch03x1::Chapter03x1, ch04::Chapter04,
+    ch05::Chapter05, ch06::Chapter06,
+};
+
+use crate::{cli::Entry, FrameContext};
+
+/// How far the animated chapters' `t` advances per second.
+const SPEED: f32 = 0.2;
+
+/// Every chapter, in the order `--list` prints them.
+pub const CHAPTERS: &[Entry] = &[
+    ch01::ENTRY,
+    ch02::ENTRY,
+    ch03::ENTRY,
+    ch03x1::ENTRY,
+    ch04::ENTRY,
+    ch05::ENTRY,
+    ch06::ENTRY,
+];
+
+/// Looks up a chapter by the name `--chapter` takes.
+pub fn find(name: &str) -> Option<&'static Entry> {
+    CHAPTERS.iter().find(|entry| entry.name == name)
+}
+
+/// Advances the `t` the animated chapters move the triangle by, wrapping it
+/// around from 0.5 to -0.5.
+fn animate_t(ctx: &mut FrameContext<f32>) {
+    ctx.user += SPEED * ctx.delta;
+    if ctx.user > 0.5 {
+        ctx.user -= 1.0;
+    }
+}
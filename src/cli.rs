@@ -0,0 +1,279 @@
+//! The command line of the `tutorial` binary.
+
+use std::path::PathBuf;
+
+use crate::{
+    adapter::{self, AdapterSelector},
+    chapters,
+    config::PresentMode,
+    error::Error,
+    init_logger,
+    input::KeyBindings,
+    App, Camera, Chapter, Headless, Redraw, RendyConfig,
+};
+
+pub const USAGE: &str = "\
+usage: tutorial --chapter <name> [options]
+       tutorial --list
+       tutorial --list-adapters
+
+options:
+    --chapter <name>       the chapter to run, see --list
+    --list                 list the chapters and exit
+    --list-adapters        list the GPUs rendy can use and exit
+    --adapter <adapter>    the GPU to use, by index or part of its name
+    --size <w>x<h>         size of the window or the rendered image
+    --camera <camera>      fixed, orbit or fly
+    --key-bindings <file>  TOML file rebinding the camera controls
+    --hot-reload           reload shaders when their sources change
+    --redraw <mode>        continuous or on-demand, by default continuous only
+                           for animated chapters
+    --config <file>        TOML file configuring rendy's device, queues and heaps
+    --set <key>=<value>    override a key of the rendy configuration
+    --present-mode <mode>  fifo, mailbox, immediate or relaxed
+    --image-count <n>      number of swapchain images
+    --headless             render offscreen instead of opening a window
+    --frames <n>           frames to render before saving, with --headless
+    --out <file>           image to save to, with --headless";
+
+/// Size of the rendered image when `--headless` is given without `--size`.
+pub const DEFAULT_HEADLESS_SIZE: (u32, u32) = (800, 600);
+
+/// A chapter the `tutorial` binary can run.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    /// What `--chapter` selects the chapter by.
+    pub name: &'static str,
+    /// One line shown by `--list`.
+    pub description: &'static str,
+    pub(crate) launch: fn(&Options) -> Result<(), Error>,
+}
+
+impl Entry {
+    pub fn launch(&self, options: &Options) -> Result<(), Error> {
+        (self.launch)(options)
+    }
+}
+
+impl std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+/// Runs chapter `C` the way `options` asks for.
+pub(crate) fn launch<C: Chapter + Default>(options: &Options) -> Result<(), Error> {
+    launch_with(C::default(), options)
+}
+
+/// Runs `chapter` the way `options` asks for. Used by chapters that can fail
+/// to construct.
+pub(crate) fn launch_with<C: Chapter>(chapter: C, options: &Options) -> Result<(), Error> {
+    let mut config = RendyConfig::load(options.config.as_deref(), &options.overrides)?;
+    if let Some(adapter) = &options.adapter {
+        config.device.adapter = Some(adapter.clone());
+    }
+    if let Some(mode) = options.present_mode {
+        config.present.mode = Some(mode);
+    }
+    if let Some(count) = options.image_count {
+        config.present.image_count = Some(count);
+    }
+
+    if options.headless {
+        init_logger();
+        let (width, height) = options.size.unwrap_or(DEFAULT_HEADLESS_SIZE);
+        let mut headless = Headless::new(width, height)
+            .with_frames(options.frames)
+            .with_config(config);
+        if let Some(camera) = &options.camera {
+            headless = headless.with_camera(camera.clone());
+        }
+        return headless.render_to_file(chapter, &options.out);
+    }
+
+    let mut app = App::new(chapter).with_config(config);
+    if let Some((width, height)) = options.size {
+        app = app.with_size(width, height);
+    }
+    if let Some(camera) = &options.camera {
+        app = app.with_camera(camera.clone());
+    }
+    if let Some(path) = &options.key_bindings {
+        app = app.with_key_bindings(KeyBindings::load(path)?);
+    }
+    if options.hot_reload {
+        app = app.with_hot_reload(true);
+    }
+    if let Some(redraw) = options.redraw {
+        app = app.with_redraw(redraw);
+    }
+    app.run()
+}
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub chapter: Option<String>,
+    pub list: bool,
+    pub list_adapters: bool,
+    pub adapter: Option<AdapterSelector>,
+    pub size: Option<(u32, u32)>,
+    pub camera: Option<Camera>,
+    pub key_bindings: Option<PathBuf>,
+    pub hot_reload: bool,
+    pub redraw: Option<Redraw>,
+    pub config: Option<PathBuf>,
+    /// `key=value` pairs set on top of the rendy configuration.
+    pub overrides: Vec<String>,
+    pub present_mode: Option<PresentMode>,
+    pub image_count: Option<u32>,
+    pub headless: bool,
+    pub frames: usize,
+    pub out: PathBuf,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            chapter: None,
+            list: false,
+            list_adapters: false,
+            adapter: None,
+            size: None,
+            camera: None,
+            key_bindings: None,
+            hot_reload: false,
+            redraw: None,
+            config: None,
+            overrides: Vec::new(),
+            present_mode: None,
+            image_count: None,
+            headless: false,
+            frames: 1,
+            out: "frame.png".into(),
+        }
+    }
+}
+
+impl Options {
+    /// Parses the arguments following the program name.
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+
+            match arg.as_str() {
+                "--chapter" => options.chapter = Some(value()?),
+                "--list" => options.list = true,
+                "--list-adapters" => options.list_adapters = true,
+                "--adapter" => options.adapter = Some(value()?.as_str().into()),
+                "--size" => options.size = Some(parse_size(&value()?)?),
+                "--camera" => {
+                    let name = value()?;
+                    options.camera = Some(
+                        Camera::from_name(&name)
+                            .ok_or_else(|| format!("unknown camera `{}`", name))?,
+                    );
+                }
+                "--key-bindings" => options.key_bindings = Some(value()?.into()),
+                "--hot-reload" => options.hot_reload = true,
+                "--redraw" => options.redraw = Some(value()?.parse()?),
+                "--config" => options.config = Some(value()?.into()),
+                "--set" => options.overrides.push(value()?),
+                "--present-mode" => options.present_mode = Some(value()?.parse()?),
+                "--image-count" => {
+                    let count = value()?;
+                    options.image_count = Some(
+                        count
+                            .parse()
+                            .map_err(|_| format!("`{}` is not a number of images", count))?,
+                    );
+                }
+                "--headless" => options.headless = true,
+                "--frames" => {
+                    let frames = value()?;
+                    options.frames = frames
+                        .parse()
+                        .map_err(|_| format!("`{}` is not a number of frames", frames))?;
+                }
+                "--out" => options.out = value()?.into(),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+
+        if options.chapter.is_none() && !options.list && !options.list_adapters {
+            return Err("no chapter given".into());
+        }
+
+        if options.headless {
+            let window_only = [
+                ("--key-bindings", options.key_bindings.is_some()),
+                ("--hot-reload", options.hot_reload),
+                ("--redraw", options.redraw.is_some()),
+                ("--present-mode", options.present_mode.is_some()),
+                ("--image-count", options.image_count.is_some()),
+            ];
+            if let Some((flag, _)) = window_only.iter().find(|(_, given)| *given) {
+                return Err(format!(
+                    "{} needs a window, it can't be used with --headless",
+                    flag
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Parses a size given as `<width>x<height>`.
+pub fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("`{}` is not a size like 1280x720", size);
+
+    let mut dims = size.split('x');
+    match (dims.next(), dims.next(), dims.next()) {
+        (Some(width), Some(height), None) => {
+            let width = width.parse().map_err(|_| invalid())?;
+            let height = height.parse().map_err(|_| invalid())?;
+            if width == 0 || height == 0 {
+                return Err(invalid());
+            }
+            Ok((width, height))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Lists the chapters or adapters, or runs the one `options` selects.
+pub fn run(options: &Options) -> Result<(), Error> {
+    if options.list {
+        for entry in chapters::CHAPTERS {
+            println!("{:<6}{}", entry.name, entry.description);
+        }
+        return Ok(());
+    }
+
+    if options.list_adapters {
+        init_logger();
+        for adapter in adapter::list()? {
+            println!("{}", adapter);
+        }
+        return Ok(());
+    }
+
+    let name = options.chapter.as_deref().unwrap_or("");
+    match chapters::find(name) {
+        Some(entry) => entry.launch(options),
+        None => Err(Error::UnknownChapter(name.to_string())),
+    }
+}
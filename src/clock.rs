@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+
+/// How a `Clock` measures the time between two ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+    /// Wall time elapsed since the previous tick.
+    RealTime,
+    /// The same delta on every tick, regardless of wall time.
+    Fixed(Duration),
+}
+
+/// Drives animations independently of how fast the event loop spins.
+#[derive(Clone, Debug)]
+pub struct Clock {
+    step: Step,
+    last: Option<Instant>,
+    wall: Duration,
+    wall_delta: Duration,
+    elapsed: Duration,
+    delta: Duration,
+    scale: f32,
+    paused: bool,
+    frame: u64,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock::with_step(Step::RealTime)
+    }
+
+    /// A clock advancing by `step` on every tick. Used for deterministic rendering in tests.
+    pub fn fixed(step: Duration) -> Self {
+        Clock::with_step(Step::Fixed(step))
+    }
+
+    pub fn with_step(step: Step) -> Self {
+        Clock {
+            step,
+            last: None,
+            wall: Duration::from_secs(0),
+            wall_delta: Duration::from_secs(0),
+            elapsed: Duration::from_secs(0),
+            delta: Duration::from_secs(0),
+            scale: 1.0,
+            paused: false,
+            frame: 0,
+        }
+    }
+
+    /// Advances the clock by one frame.
+    pub fn tick(&mut self) {
+        let raw = match self.step {
+            Step::RealTime => {
+                let now = Instant::now();
+                let raw = self
+                    .last
+                    .map_or(Duration::from_secs(0), |last| now.duration_since(last));
+                self.last = Some(now);
+                raw
+            }
+            Step::Fixed(step) => step,
+        };
+
+        self.wall += raw;
+        self.wall_delta = raw;
+        self.delta = if self.paused {
+            Duration::from_secs(0)
+        } else {
+            raw.mul_f32(self.scale)
+        };
+        self.elapsed += self.delta;
+        self.frame += 1;
+    }
+
+    /// Makes the next tick of a real time clock count no time, instead of
+    /// the time since the previous tick. Used when the clock was not ticked
+    /// while the event loop waited for events.
+    pub fn skip_gap(&mut self) {
+        self.last = None;
+    }
+
+    /// Scaled time since the clock started, excluding time spent paused.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Scaled time between the last two ticks. Zero while paused.
+    pub fn delta(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Unscaled time since the clock started, including time spent paused.
+    pub fn wall(&self) -> f32 {
+        self.wall.as_secs_f32()
+    }
+
+    /// Unscaled time between the last two ticks, including while paused.
+    pub fn wall_delta(&self) -> f32 {
+        self.wall_delta.as_secs_f32()
+    }
+
+    /// Number of ticks so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn step(&self) -> Step {
+        self.step
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets how fast time passes relative to wall time. Negative scales stop
+    /// time like a scale of zero.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn pause(&mut self) {
+        self.set_paused(true);
+    }
+
+    pub fn resume(&mut self) {
+        self.set_paused(false);
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::new()
+    }
+}
@@ -0,0 +1,477 @@
+//! The device, queue and memory configuration rendy is initialized with.
+//!
+//! Everything is optional and falls back to what rendy's `Config::default()`
+//! picks. The configuration is read from a TOML file like
+//!
+//! ```toml
+//! [device]
+//! adapter = "intel"
+//! type = "integrated"
+//!
+//! [queues]
+//! family = 0
+//! count = 2
+//!
+//! [heaps]
+//! linear_size = 16777216
+//! max_chunk_size = 67108864
+//!
+//! [present]
+//! mode = "mailbox"
+//! image_count = 2
+//! ```
+//!
+//! and single keys can be overridden on top of it, as in
+//! `heaps.linear_size=33554432`.
+
+use std::{ops::RangeInclusive, path::Path, str::FromStr};
+
+use rendy::{
+    command::FamilyId,
+    core::DeviceId,
+    factory::{
+        BasicDevicesConfigure, BasicHeapsConfigure, Config, DevicesConfigure, HeapsConfigure,
+        QueuesConfigure,
+    },
+    hal::{
+        self,
+        adapter::{Adapter, AdapterInfo, MemoryProperties},
+        queue::QueueFamily,
+    },
+    memory::HeapsConfig,
+};
+use serde::Deserialize;
+
+use crate::{
+    adapter::{self, AdapterSelector},
+    error::{Error, Result},
+};
+
+/// How rendy picks the device, sets up its queues and memory allocators and
+/// presents to the window.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RendyConfig {
+    pub device: DeviceConfig,
+    pub queues: QueueConfig,
+    pub heaps: HeapConfig,
+    pub present: PresentConfig,
+}
+
+impl RendyConfig {
+    /// Reads the file at `path`, if any, and applies `overrides` on top of it.
+    pub fn load(path: Option<&Path>, overrides: &[String]) -> Result<Self> {
+        let (path, source) = match path {
+            Some(path) => {
+                let source = std::fs::read_to_string(path).map_err(|cause| Error::Asset {
+                    path: path.display().to_string(),
+                    cause,
+                })?;
+                (path.display().to_string(), source)
+            }
+            None => ("the rendy config".into(), String::new()),
+        };
+
+        RendyConfig::parse(&source, overrides).map_err(|message| Error::Config { path, message })
+    }
+
+    /// Parses a TOML configuration, then sets every `key=value` in
+    /// `overrides`. Keys are dotted paths into the configuration and values
+    /// are TOML values.
+    pub fn parse(source: &str, overrides: &[String]) -> std::result::Result<Self, String> {
+        let mut config: toml::Value = toml::from_str(source).map_err(|err| err.to_string())?;
+
+        for line in overrides {
+            let (key, value) = parse_override(line)?;
+            set(&mut config, &key, value).map_err(|err| format!("{} in `{}`", err, line))?;
+        }
+
+        let config: RendyConfig = config.try_into().map_err(|err| err.to_string())?;
+        config.heaps.validate()?;
+        Ok(config)
+    }
+
+    /// The configuration in the form rendy's initialization takes. Fails if
+    /// the configured adapter doesn't exist.
+    pub fn factory_config(&self) -> Result<Config<DeviceConfig, HeapConfig, QueueConfig>> {
+        self.device.check()?;
+        Ok(Config {
+            devices: self.device.clone(),
+            heaps: self.heaps.clone(),
+            queues: self.queues.clone(),
+        })
+    }
+}
+
+fn parse_override(line: &str) -> std::result::Result<(Vec<String>, toml::Value), String> {
+    let eq = line
+        .find('=')
+        .ok_or_else(|| format!("`{}` is not a key=value pair", line))?;
+    let (key, value) = (line[..eq].trim(), line[eq + 1..].trim());
+    if key.is_empty() {
+        return Err(format!("`{}` has no key", line));
+    }
+
+    // Parsed as a TOML value where possible, so numbers stay numbers, and
+    // taken as a string otherwise.
+    let value = toml::from_str::<toml::Value>(&format!("value = {}", value))
+        .ok()
+        .and_then(|table| table.get("value").cloned())
+        .unwrap_or_else(|| toml::Value::String(value.into()));
+
+    Ok((key.split('.').map(String::from).collect(), value))
+}
+
+fn set(
+    table: &mut toml::Value,
+    key: &[String],
+    value: toml::Value,
+) -> std::result::Result<(), String> {
+    let table = table
+        .as_table_mut()
+        .ok_or_else(|| "a value is not a table".to_string())?;
+    let (first, rest) = key.split_first().expect("keys are never empty");
+    if rest.is_empty() {
+        table.insert(first.clone(), value);
+        return Ok(());
+    }
+    set(
+        table
+            .entry(first.clone())
+            .or_insert_with(|| toml::Value::Table(Default::default())),
+        rest,
+        value,
+    )
+}
+
+/// A kind of physical device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceType {
+    Discrete,
+    Integrated,
+    Virtual,
+    Cpu,
+}
+
+impl DeviceType {
+    fn to_hal(self) -> hal::adapter::DeviceType {
+        match self {
+            DeviceType::Discrete => hal::adapter::DeviceType::DiscreteGpu,
+            DeviceType::Integrated => hal::adapter::DeviceType::IntegratedGpu,
+            DeviceType::Virtual => hal::adapter::DeviceType::VirtualGpu,
+            DeviceType::Cpu => hal::adapter::DeviceType::Cpu,
+        }
+    }
+}
+
+/// Which adapter to create the device on. rendy prefers a discrete GPU.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeviceConfig {
+    /// Picks this adapter, taking precedence over `type`.
+    pub adapter: Option<AdapterSelector>,
+    /// Picks the first adapter of this type.
+    #[serde(rename = "type")]
+    pub device_type: Option<DeviceType>,
+}
+
+impl DeviceConfig {
+    /// The index of the configured adapter in `adapters`, or `None` to leave
+    /// the choice to rendy. An adapter that isn't in `adapters` is an error,
+    /// a device type that isn't is only a warning.
+    pub fn select(&self, adapters: &[AdapterInfo]) -> std::result::Result<Option<usize>, String> {
+        if let Some(selector) = &self.adapter {
+            return selector
+                .find(adapters)
+                .map(Some)
+                .ok_or_else(|| format!("There is no {}", selector));
+        }
+
+        if let Some(device_type) = self.device_type {
+            let found = adapters
+                .iter()
+                .position(|adapter| adapter.device_type == device_type.to_hal());
+            match found {
+                Some(index) => return Ok(Some(index)),
+                None => log::warn!("There is no {:?} adapter", device_type),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Checks that the configured adapter exists. rendy can't fail while
+    /// picking an adapter, so this has to happen before it creates the device.
+    pub fn check(&self) -> Result<()> {
+        if self.adapter.is_none() {
+            return Ok(());
+        }
+        let adapters: Vec<_> = adapter::list()?
+            .into_iter()
+            .map(|adapter| adapter.info)
+            .collect();
+        self.select(&adapters).map(drop).map_err(Error::Init)
+    }
+}
+
+impl DevicesConfigure for DeviceConfig {
+    fn pick<B: hal::Backend>(&self, adapters: &[Adapter<B>]) -> usize {
+        let infos: Vec<_> = adapters
+            .iter()
+            .map(|adapter| adapter.info.clone())
+            .collect();
+        let index = match self.select(&infos) {
+            Ok(Some(index)) => index,
+            Ok(None) => BasicDevicesConfigure.pick(adapters),
+            // `check` rejects these before the device is created.
+            Err(message) => panic!("{}", message),
+        };
+
+        let info = &infos[index];
+        log::info!(
+            "Using adapter {}: {} ({:?})",
+            index,
+            info.name,
+            info.device_type
+        );
+        index
+    }
+}
+
+/// The queues created on the device. rendy creates one queue of the first
+/// family supporting graphics.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueueConfig {
+    /// Index of the queue family, which has to support graphics.
+    pub family: Option<usize>,
+    /// Number of queues, capped at what the family offers.
+    pub count: usize,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        QueueConfig {
+            family: None,
+            count: 1,
+        }
+    }
+}
+
+unsafe impl QueuesConfigure for QueueConfig {
+    type Priorities = Vec<f32>;
+    type Families = Option<(FamilyId, Vec<f32>)>;
+
+    fn configure(&self, device: DeviceId, families: &[impl QueueFamily]) -> Self::Families {
+        let configured = self.family.and_then(|index| {
+            let family = families
+                .get(index)
+                .filter(|family| supports_graphics(*family));
+            if family.is_none() {
+                log::warn!("Queue family {} can't be used for graphics", index);
+            }
+            family
+        });
+        let family =
+            configured.or_else(|| families.iter().find(|family| supports_graphics(*family)))?;
+
+        let count = self.count.max(1).min(family.max_queues());
+        Some((
+            FamilyId {
+                index: family.id().0,
+                device,
+            },
+            vec![1.0; count],
+        ))
+    }
+}
+
+fn supports_graphics(family: &impl QueueFamily) -> bool {
+    family.queue_type().supports_graphics() && family.max_queues() > 0
+}
+
+/// Memory allocator sizes, in bytes. Unset sizes are derived from the size
+/// of each heap by rendy. The allocator sizes have to be powers of two, the
+/// heap cap does not.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeapConfig {
+    /// Caps how much of each memory heap is used.
+    pub max_heap_size: Option<u64>,
+    /// Size of the pages the linear (arena) allocator hands out short-lived
+    /// host visible allocations from.
+    pub linear_size: Option<u64>,
+    /// Granularity of the blocks the dynamic allocator hands out.
+    pub block_size_granularity: Option<u64>,
+    /// Largest chunk the dynamic allocator splits into blocks.
+    pub max_chunk_size: Option<u64>,
+    /// Smallest allocation the dynamic allocator requests from the device.
+    pub min_device_allocation: Option<u64>,
+}
+
+impl HeapConfig {
+    /// Checks the sizes rendy's allocators would otherwise assert on.
+    fn validate(&self) -> std::result::Result<(), String> {
+        let sizes = [
+            ("linear_size", self.linear_size),
+            ("block_size_granularity", self.block_size_granularity),
+            ("max_chunk_size", self.max_chunk_size),
+            ("min_device_allocation", self.min_device_allocation),
+        ];
+        let set = sizes
+            .iter()
+            .filter_map(|(key, size)| size.map(|size| (key, size)));
+        for (key, size) in set {
+            if !size.is_power_of_two() {
+                return Err(format!(
+                    "heaps.{} must be a power of two, not {}",
+                    key, size
+                ));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (self.min_device_allocation, self.max_chunk_size) {
+            if min > max {
+                return Err(format!(
+                    "heaps.min_device_allocation ({}) is larger than heaps.max_chunk_size ({})",
+                    min, max
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+unsafe impl HeapsConfigure for HeapConfig {
+    type Types = Vec<(hal::memory::Properties, u32, HeapsConfig)>;
+    type Heaps = Vec<u64>;
+
+    fn configure(&self, properties: &MemoryProperties) -> (Self::Types, Self::Heaps) {
+        let (mut types, mut heaps) = BasicHeapsConfigure.configure(properties);
+
+        for (_, _, config) in &mut types {
+            if let (Some(linear), Some(size)) = (&mut config.linear, self.linear_size) {
+                linear.linear_size = size;
+            }
+            if let Some(dynamic) = &mut config.dynamic {
+                if let Some(size) = self.block_size_granularity {
+                    dynamic.block_size_granularity = size;
+                }
+                if let Some(size) = self.max_chunk_size {
+                    dynamic.max_chunk_size = size;
+                }
+                if let Some(size) = self.min_device_allocation {
+                    dynamic.min_device_allocation = size;
+                }
+            }
+        }
+
+        if let Some(max) = self.max_heap_size {
+            for heap in &mut heaps {
+                *heap = (*heap).min(max);
+            }
+        }
+
+        (types, heaps)
+    }
+}
+
+/// How images are queued for presentation. Fifo waits for vertical blank and
+/// is the only mode every driver supports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PresentMode {
+    Fifo,
+    Mailbox,
+    Immediate,
+    Relaxed,
+}
+
+impl PresentMode {
+    /// The modes to try in order when this one is requested, starting with
+    /// itself and moving on to the ones with similar latency and tearing.
+    fn fallbacks(self) -> [PresentMode; 4] {
+        use PresentMode::*;
+        match self {
+            Fifo => [Fifo, Relaxed, Mailbox, Immediate],
+            Mailbox => [Mailbox, Immediate, Fifo, Relaxed],
+            Immediate => [Immediate, Mailbox, Relaxed, Fifo],
+            Relaxed => [Relaxed, Fifo, Immediate, Mailbox],
+        }
+    }
+
+    fn to_hal(self) -> hal::window::PresentMode {
+        match self {
+            PresentMode::Fifo => hal::window::PresentMode::FIFO,
+            PresentMode::Mailbox => hal::window::PresentMode::MAILBOX,
+            PresentMode::Immediate => hal::window::PresentMode::IMMEDIATE,
+            PresentMode::Relaxed => hal::window::PresentMode::RELAXED,
+        }
+    }
+}
+
+impl FromStr for PresentMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, String> {
+        match name {
+            "fifo" => Ok(PresentMode::Fifo),
+            "mailbox" => Ok(PresentMode::Mailbox),
+            "immediate" => Ok(PresentMode::Immediate),
+            "relaxed" => Ok(PresentMode::Relaxed),
+            _ => Err(format!("unknown present mode `{}`", name)),
+        }
+    }
+}
+
+/// Swapchain image count used when none is configured, the same as rendy's.
+pub const DEFAULT_IMAGE_COUNT: u32 = 3;
+
+/// How the window's swapchain is set up.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresentConfig {
+    /// Defaults to fifo.
+    pub mode: Option<PresentMode>,
+    /// Number of swapchain images, capped at what the surface allows.
+    pub image_count: Option<u32>,
+}
+
+impl PresentConfig {
+    /// The configured mode if the surface supports it, or the closest one it
+    /// does. `supported` holds every mode the surface supports.
+    pub fn choose_mode(&self, supported: hal::window::PresentMode) -> hal::window::PresentMode {
+        let wanted = self.mode.unwrap_or(PresentMode::Fifo);
+        let chosen = wanted
+            .fallbacks()
+            .iter()
+            .map(|mode| mode.to_hal())
+            .find(|mode| supported.contains(*mode))
+            .unwrap_or(hal::window::PresentMode::FIFO);
+
+        if chosen != wanted.to_hal() {
+            log::warn!(
+                "Present mode {:?} is not supported, falling back to {:?}",
+                wanted,
+                chosen
+            );
+        }
+        chosen
+    }
+
+    /// The configured image count within the `supported` range.
+    pub fn choose_image_count(&self, supported: RangeInclusive<u32>) -> u32 {
+        let wanted = self.image_count.unwrap_or(DEFAULT_IMAGE_COUNT);
+        let chosen = wanted.max(*supported.start()).min(*supported.end());
+
+        if self.image_count.is_some() && chosen != wanted {
+            log::warn!(
+                "{} swapchain images are not supported, using {}",
+                wanted,
+                chosen
+            );
+        }
+        chosen
+    }
+}
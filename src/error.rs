@@ -0,0 +1,114 @@
+use std::fmt;
+
+use failure::Fail;
+use rendy::{graph::render::PrepareResult, hal};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Fail)]
+pub enum Error {
+    #[fail(display = "Unable to initialize rendy: {}", _0)]
+    Init(String),
+
+    #[fail(display = "Unknown chapter `{}`, see --list", _0)]
+    UnknownChapter(String),
+
+    #[fail(display = "Unable to compile shader {}: {}", path, message)]
+    ShaderCompile { path: String, message: String },
+
+    #[fail(display = "Unable to reflect shaders: {}", _0)]
+    Reflection(String),
+
+    #[fail(
+        display = "Vertex type {} does not match the vertex shader inputs:\n{}",
+        vertex, details
+    )]
+    VertexLayout {
+        vertex: &'static str,
+        details: String,
+    },
+
+    #[fail(display = "Unable to bind {}: {}", name, message)]
+    Binding { name: String, message: String },
+
+    #[fail(
+        display = "Uniform type {} does not match the shader block:\n{}",
+        uniform, details
+    )]
+    UniformLayout {
+        uniform: &'static str,
+        details: String,
+    },
+
+    #[fail(display = "Invalid push constants: {}", _0)]
+    PushConstants(String),
+
+    #[fail(display = "Unable to create {}: {}", what, message)]
+    Resource { what: &'static str, message: String },
+
+    #[fail(display = "Unable to read {}", path)]
+    Asset {
+        path: String,
+        #[cause]
+        cause: std::io::Error,
+    },
+
+    #[fail(display = "Unable to parse {}: {}", path, message)]
+    Config { path: String, message: String },
+
+    #[fail(display = "Unable to decode image {}: {}", path, message)]
+    Image { path: String, message: String },
+
+    #[fail(display = "Unable to build render graph: {}", _0)]
+    Graph(String),
+
+    #[fail(display = "Unable to read back the rendered image: {}", _0)]
+    Readback(String),
+
+    #[fail(display = "Unable to write {}", path)]
+    Output {
+        path: String,
+        #[cause]
+        cause: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Wraps an error from a rendy call that failed while creating `what`.
+    pub fn resource<E: fmt::Debug>(what: &'static str) -> impl FnOnce(E) -> Error {
+        move |err| Error::Resource {
+            what,
+            message: format!("{:?}", err),
+        }
+    }
+}
+
+/// Logs an error raised inside a pipeline builder and collapses it into the
+/// error type rendy expects there.
+pub(crate) fn creation_error<E: fmt::Debug>(
+    what: &'static str,
+) -> impl FnOnce(E) -> hal::pso::CreationError {
+    move |err| {
+        log::error!("Unable to create {}: {:?}", what, err);
+        hal::pso::CreationError::Other
+    }
+}
+
+/// Logs an error raised while writing a frame's buffers in `prepare`. The
+/// frame is drawn with whatever the buffers held before.
+pub(crate) fn prepare_error(err: Error) -> PrepareResult {
+    log::error!("Unable to prepare the frame: {:?}", err);
+    PrepareResult::DrawReuse
+}
+
+// Prints the whole cause chain, so returning an `Error` from `main` gives a
+// readable message.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)?;
+        for cause in <dyn Fail>::iter_causes(self) {
+            write!(f, "\n    caused by: {}", cause)?;
+        }
+        Ok(())
+    }
+}
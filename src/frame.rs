@@ -0,0 +1,53 @@
+use rendy::hal::window::Extent2D;
+
+use crate::{input::Input, projection::Projection, Clock};
+
+/// The aux value the harness builds and runs every chapter's graph with.
+///
+/// Everything a node needs to know about the current frame is in here, with
+/// the chapter's own state in `user`.
+#[derive(Clone, Debug)]
+pub struct FrameContext<A> {
+    /// Scaled seconds since the clock started, see `Clock::elapsed`.
+    pub time: f32,
+    /// Scaled seconds since the previous frame, see `Clock::delta`.
+    pub delta: f32,
+    /// Counts the frames rendered, starting at 1 for the first one.
+    pub frame: u64,
+    /// Size of the framebuffer being rendered to.
+    pub extent: Extent2D,
+    /// The camera's matrices, written to each chapter's `Projection` block.
+    pub camera: Projection,
+    /// What the user did since the previous frame.
+    pub input: Input,
+    /// The chapter's own aux value, advanced by `Chapter::update`.
+    pub user: A,
+}
+
+impl<A> FrameContext<A> {
+    /// The context before the first frame, with an empty framebuffer.
+    pub fn new(user: A, input: Input) -> Self {
+        let extent = Extent2D {
+            width: 0,
+            height: 0,
+        };
+
+        FrameContext {
+            time: 0.0,
+            delta: 0.0,
+            frame: 0,
+            extent,
+            camera: Projection::for_extent(extent),
+            input,
+            user,
+        }
+    }
+
+    /// Moves on to the frame `clock` just ticked to, rendered at `extent`.
+    pub fn tick(&mut self, clock: &Clock, extent: Extent2D) {
+        self.time = clock.elapsed();
+        self.delta = clock.delta();
+        self.frame = clock.frame();
+        self.extent = extent;
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use image::RgbaImage;
+use rendy::{
+    command::{
+        CommandBuffer, CommandPool, ExecutableState, Families, Family, Graphics, MultiShot,
+        PendingState, SimultaneousUse, Submit,
+    },
+    factory::Factory,
+    frame::Frames,
+    graph::{
+        gfx_acquire_barriers, gfx_release_barriers, BufferAccess, GraphBuilder, GraphContext,
+        ImageAccess, Node, NodeBuffer, NodeBuildError, NodeDesc, NodeImage, NodeSubmittable,
+    },
+    hal::{self, command::CommandBuffer as _, device::Device as _},
+    init::AnyRendy,
+    memory::Download,
+    resource::{Buffer, BufferInfo, Escape},
+};
+
+use crate::{
+    app::CLEAR_COLOR, error::Error, input::Input, Camera, Chapter, Clock, FrameContext, RendyConfig,
+};
+
+/// Format of the offscreen color target. Matches the byte layout of `RgbaImage`.
+pub const FORMAT: hal::format::Format = hal::format::Format::Rgba8Srgb;
+
+/// Renders a chapter into an offscreen image instead of a window.
+#[derive(Clone, Debug)]
+pub struct Headless {
+    width: u32,
+    height: u32,
+    frames: usize,
+    step: Duration,
+    camera: Camera,
+    config: RendyConfig,
+}
+
+impl Headless {
+    pub fn new(width: u32, height: u32) -> Self {
+        Headless {
+            width,
+            height,
+            frames: 1,
+            step: Duration::from_secs(1) / 60,
+            camera: Camera::default(),
+            config: RendyConfig::default(),
+        }
+    }
+
+    /// Number of frames to run before reading back the color image.
+    pub fn with_frames(mut self, frames: usize) -> Self {
+        self.frames = frames.max(1);
+        self
+    }
+
+    /// Time the clock advances per frame.
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The camera looking at the chapter. Nothing moves it, so it keeps its
+    /// initial position.
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// How rendy picks the device and sets up its queues and memory.
+    pub fn with_config(mut self, config: RendyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn render<C>(&self, chapter: C) -> Result<RgbaImage, Error>
+    where
+        C: Chapter,
+    {
+        self.render_inner(chapter, None)
+    }
+
+    /// Renders every frame with a fixed `FrameContext::user`. `Chapter::update`
+    /// is never called.
+    pub fn render_at<C>(&self, chapter: C, aux: C::Aux) -> Result<RgbaImage, Error>
+    where
+        C: Chapter,
+    {
+        self.render_inner(chapter, Some(aux))
+    }
+
+    fn render_inner<C>(&self, chapter: C, aux: Option<C::Aux>) -> Result<RgbaImage, Error>
+    where
+        C: Chapter,
+    {
+        let config = self.config.factory_config()?;
+
+        let rendy =
+            AnyRendy::init_auto(&config).map_err(|err| Error::Init(format!("{:?}", err)))?;
+        rendy::with_any_rendy!((rendy)
+            (factory, families) => {
+                render_offscreen(self, chapter, aux, factory, families)
+            }
+        )
+    }
+
+    pub fn render_to_file<C>(&self, chapter: C, path: impl AsRef<Path>) -> Result<(), Error>
+    where
+        C: Chapter,
+    {
+        let path = path.as_ref();
+        self.render(chapter)?
+            .save(path)
+            .map_err(|cause| Error::Output {
+                path: path.display().to_string(),
+                cause,
+            })?;
+        log::info!("Wrote {}", path.display());
+        Ok(())
+    }
+}
+
+fn render_offscreen<B, C>(
+    headless: &Headless,
+    mut chapter: C,
+    aux: Option<C::Aux>,
+    mut factory: Factory<B>,
+    mut families: Families<B>,
+) -> Result<RgbaImage, Error>
+where
+    B: hal::Backend,
+    C: Chapter,
+{
+    let mut graph_builder = GraphBuilder::<B, FrameContext<C::Aux>>::new();
+
+    let color = graph_builder.create_image(
+        hal::image::Kind::D2(headless.width, headless.height, 1, 1),
+        1,
+        FORMAT,
+        Some(CLEAR_COLOR),
+    );
+
+    let pass = graph_builder.add_node(chapter.subpass()?.with_color(color).into_pass());
+
+    let size = headless.width as u64 * headless.height as u64 * 4;
+    let target = Arc::new(Mutex::new(
+        factory
+            .create_buffer(
+                BufferInfo {
+                    size,
+                    usage: hal::buffer::Usage::TRANSFER_DST,
+                },
+                Download,
+            )
+            .map_err(Error::resource("readback buffer"))?,
+    ));
+
+    graph_builder.add_node(
+        ReadbackDesc {
+            target: target.clone(),
+            extent: hal::image::Extent {
+                width: headless.width,
+                height: headless.height,
+                depth: 1,
+            },
+        }
+        .builder()
+        .with_image(color)
+        .with_dependency(pass),
+    );
+
+    let mut clock = Clock::fixed(headless.step);
+    let fixed = aux.is_some();
+    let extent = hal::window::Extent2D {
+        width: headless.width,
+        height: headless.height,
+    };
+    let mut aux = FrameContext::new(aux.unwrap_or_else(|| chapter.aux()), Input::default());
+    aux.camera = headless.camera.projection(extent);
+
+    let mut graph = graph_builder
+        .build(&mut factory, &mut families, &aux)
+        .map_err(|err| Error::Graph(format!("{:?}", err)))?;
+
+    for _ in 0..headless.frames {
+        factory.maintain(&mut families);
+        clock.tick();
+        aux.tick(&clock, extent);
+
+        if !fixed {
+            chapter.update(&mut aux);
+        }
+
+        graph.run(&mut factory, &mut families, &aux);
+    }
+
+    let pixels = factory
+        .device()
+        .wait_idle()
+        .map_err(readback_error)
+        .and_then(|()| {
+            let mut target = target.lock().unwrap();
+            unsafe {
+                let mut mapped = target
+                    .map(factory.device(), 0..size)
+                    .map_err(readback_error)?;
+                let pixels = mapped
+                    .read::<u8>(factory.device(), 0..size)
+                    .map_err(readback_error)?;
+                Ok(pixels.to_vec())
+            }
+        });
+
+    graph.dispose(&mut factory, &aux);
+
+    Ok(RgbaImage::from_raw(headless.width, headless.height, pixels?).unwrap())
+}
+
+fn readback_error<E: std::fmt::Debug>(err: E) -> Error {
+    Error::Readback(format!("{:?}", err))
+}
+
+/// Copies the color image into a host visible buffer after every frame.
+#[derive(Debug)]
+struct ReadbackDesc<B: hal::Backend> {
+    target: Arc<Mutex<Escape<Buffer<B>>>>,
+    extent: hal::image::Extent,
+}
+
+#[derive(Debug)]
+struct Readback<B: hal::Backend> {
+    pool: CommandPool<B, Graphics>,
+    submit: Submit<B, SimultaneousUse>,
+    buffer: CommandBuffer<B, Graphics, PendingState<ExecutableState<MultiShot<SimultaneousUse>>>>,
+    _target: Arc<Mutex<Escape<Buffer<B>>>>,
+}
+
+impl<B, T> NodeDesc<B, T> for ReadbackDesc<B>
+where
+    B: hal::Backend,
+    T: ?Sized,
+{
+    type Node = Readback<B>;
+
+    fn buffers(&self) -> Vec<BufferAccess> {
+        Vec::new()
+    }
+
+    fn images(&self) -> Vec<ImageAccess> {
+        vec![ImageAccess {
+            access: hal::image::Access::TRANSFER_READ,
+            layout: hal::image::Layout::TransferSrcOptimal,
+            usage: hal::image::Usage::TRANSFER_SRC,
+            stages: hal::pso::PipelineStage::TRANSFER,
+        }]
+    }
+
+    fn build<'a>(
+        self,
+        ctx: &GraphContext<B>,
+        factory: &mut Factory<B>,
+        family: &mut Family<B>,
+        _queue: usize,
+        _aux: &T,
+        buffers: Vec<NodeBuffer>,
+        images: Vec<NodeImage>,
+    ) -> Result<Readback<B>, NodeBuildError> {
+        assert!(buffers.is_empty());
+        assert_eq!(images.len(), 1);
+
+        let image = &images[0];
+        let source = ctx.get_image(image.id).expect("Color image does not exist");
+
+        let mut pool = factory
+            .create_command_pool(family)
+            .map_err(NodeBuildError::OutOfMemory)?
+            .with_capability::<Graphics>()
+            .expect("Readback requires a graphics queue");
+
+        let initial = pool.allocate_buffers(1).pop().unwrap();
+        let mut recording = initial.begin(MultiShot(SimultaneousUse), ());
+
+        {
+            let target = self.target.lock().unwrap();
+
+            let (stages, barriers) = gfx_acquire_barriers(ctx, None, Some(image));
+            unsafe {
+                recording.encoder().pipeline_barrier(
+                    stages,
+                    hal::memory::Dependencies::empty(),
+                    barriers,
+                );
+
+                // rendy's encoder has no image to buffer copy, so it is
+                // recorded on the raw command buffer.
+                recording.raw().copy_image_to_buffer(
+                    source.raw(),
+                    image.layout,
+                    target.raw(),
+                    Some(hal::command::BufferImageCopy {
+                        buffer_offset: 0,
+                        buffer_width: 0,
+                        buffer_height: 0,
+                        image_layers: hal::image::SubresourceLayers {
+                            aspects: hal::format::Aspects::COLOR,
+                            level: 0,
+                            layers: 0..1,
+                        },
+                        image_offset: hal::image::Offset::ZERO,
+                        image_extent: self.extent,
+                    }),
+                );
+
+                let mut encoder = recording.encoder();
+                encoder.pipeline_barrier(
+                    hal::pso::PipelineStage::TRANSFER..hal::pso::PipelineStage::HOST,
+                    hal::memory::Dependencies::empty(),
+                    Some(hal::memory::Barrier::AllBuffers(
+                        hal::buffer::Access::TRANSFER_WRITE..hal::buffer::Access::HOST_READ,
+                    )),
+                );
+
+                let (stages, barriers) = gfx_release_barriers(ctx, None, Some(image));
+                encoder.pipeline_barrier(stages, hal::memory::Dependencies::empty(), barriers);
+            }
+        }
+
+        let (submit, buffer) = recording.finish().submit();
+
+        Ok(Readback {
+            pool,
+            submit,
+            buffer,
+            _target: self.target,
+        })
+    }
+}
+
+impl<'a, B: hal::Backend> NodeSubmittable<'a, B> for Readback<B> {
+    type Submittable = &'a Submit<B, SimultaneousUse>;
+    type Submittables = Option<&'a Submit<B, SimultaneousUse>>;
+}
+
+impl<B, T> Node<B, T> for Readback<B>
+where
+    B: hal::Backend,
+    T: ?Sized,
+{
+    type Capability = Graphics;
+
+    fn run<'a>(
+        &'a mut self,
+        _ctx: &GraphContext<B>,
+        _factory: &Factory<B>,
+        _aux: &T,
+        _frames: &'a Frames<B>,
+    ) -> Option<&'a Submit<B, SimultaneousUse>> {
+        Some(&self.submit)
+    }
+
+    unsafe fn dispose(mut self, factory: &mut Factory<B>, _aux: &T) {
+        self.pool.free_buffers(Some(self.buffer.mark_complete()));
+        factory.destroy_command_pool(self.pool);
+    }
+}
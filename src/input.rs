@@ -0,0 +1,379 @@
+//! Keyboard and mouse state accumulated from winit events.
+//!
+//! The harness feeds every event to an `Input` and clears what happened
+//! during a frame once the frame is rendered, so chapters and cameras can
+//! query it instead of matching on events themselves.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+    str::FromStr,
+};
+
+use serde::{de::IntoDeserializer, Deserialize};
+use winit::event::{
+    DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode,
+    WindowEvent,
+};
+
+use crate::error::{Error, Result};
+
+/// Pixels scrolled by a touchpad that count as one wheel notch.
+const PIXELS_PER_LINE: f32 = 20.0;
+
+/// The actions the harness binds by default.
+pub mod action {
+    pub const FORWARD: &str = "forward";
+    pub const BACK: &str = "back";
+    pub const LEFT: &str = "left";
+    pub const RIGHT: &str = "right";
+    pub const UP: &str = "up";
+    pub const DOWN: &str = "down";
+    /// Drags an orbit camera, or grabs the cursor for a fly camera.
+    pub const LOOK: &str = "look";
+    /// Releases a grabbed cursor.
+    pub const RELEASE: &str = "release";
+}
+
+/// A key or mouse button.
+///
+/// Keys are named like winit's `VirtualKeyCode` variants. Mouse buttons are
+/// `MouseLeft`, `MouseRight`, `MouseMiddle` or `Mouse` followed by a number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Button {
+    Key(VirtualKeyCode),
+    Mouse(MouseButton),
+}
+
+impl FromStr for Button {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, String> {
+        if name.starts_with("Mouse") {
+            return match &name["Mouse".len()..] {
+                "Left" => Ok(Button::Mouse(MouseButton::Left)),
+                "Right" => Ok(Button::Mouse(MouseButton::Right)),
+                "Middle" => Ok(Button::Mouse(MouseButton::Middle)),
+                other => other
+                    .parse()
+                    .map(|index| Button::Mouse(MouseButton::Other(index)))
+                    .map_err(|_| format!("unknown mouse button `{}`", name)),
+            };
+        }
+
+        let deserializer: serde::de::value::StrDeserializer<'_, serde::de::value::Error> =
+            name.into_deserializer();
+        VirtualKeyCode::deserialize(deserializer)
+            .map(Button::Key)
+            .map_err(|_| format!("unknown key `{}`", name))
+    }
+}
+
+impl fmt::Display for Button {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Button::Key(key) => write!(f, "{:?}", key),
+            Button::Mouse(MouseButton::Other(index)) => write!(f, "Mouse{}", index),
+            Button::Mouse(button) => write!(f, "Mouse{:?}", button),
+        }
+    }
+}
+
+/// Maps action names to the buttons triggering them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    actions: HashMap<String, Vec<Button>>,
+}
+
+impl KeyBindings {
+    /// No bindings at all.
+    pub fn empty() -> Self {
+        KeyBindings {
+            actions: HashMap::new(),
+        }
+    }
+
+    /// The default bindings, with the actions listed in the TOML file at
+    /// `path` rebound. See `KeyBindings::parse` for the format.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|cause| Error::Asset {
+            path: path.display().to_string(),
+            cause,
+        })?;
+
+        let mut bindings = KeyBindings::default();
+        bindings.extend(
+            KeyBindings::parse(&source).map_err(|message| Error::Config {
+                path: path.display().to_string(),
+                message,
+            })?,
+        );
+        Ok(bindings)
+    }
+
+    /// Parses bindings from TOML, with every action listing its buttons:
+    ///
+    /// ```toml
+    /// forward = ["W", "Up"]
+    /// look = ["MouseRight"]
+    /// ```
+    pub fn parse(source: &str) -> std::result::Result<Self, String> {
+        let actions: HashMap<String, Vec<String>> =
+            toml::from_str(source).map_err(|err| err.to_string())?;
+
+        let actions = actions
+            .into_iter()
+            .map(|(action, buttons)| {
+                let buttons = buttons
+                    .iter()
+                    .map(|button| button.parse::<Button>())
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|err| format!("{} in `{}`", err, action))?;
+                Ok((action, buttons))
+            })
+            .collect::<std::result::Result<_, String>>()?;
+
+        Ok(KeyBindings { actions })
+    }
+
+    /// Replaces the buttons of every action bound in `other`.
+    pub fn extend(&mut self, other: KeyBindings) {
+        self.actions.extend(other.actions);
+    }
+
+    /// Adds `button` to the buttons triggering `action`.
+    pub fn bind(&mut self, action: impl Into<String>, button: Button) {
+        self.actions.entry(action.into()).or_default().push(button);
+    }
+
+    pub fn buttons(&self, action: &str) -> &[Button] {
+        self.actions.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = KeyBindings::empty();
+        let defaults = [
+            (action::FORWARD, Button::Key(VirtualKeyCode::W)),
+            (action::BACK, Button::Key(VirtualKeyCode::S)),
+            (action::LEFT, Button::Key(VirtualKeyCode::A)),
+            (action::RIGHT, Button::Key(VirtualKeyCode::D)),
+            (action::UP, Button::Key(VirtualKeyCode::Space)),
+            (action::DOWN, Button::Key(VirtualKeyCode::LShift)),
+            (action::LOOK, Button::Mouse(MouseButton::Left)),
+            (action::RELEASE, Button::Key(VirtualKeyCode::Escape)),
+        ];
+        for (action, button) in defaults.iter() {
+            bindings.bind(*action, *button);
+        }
+        bindings
+    }
+}
+
+/// The state of the keyboard and mouse, and what changed during the current
+/// frame.
+#[derive(Clone, Debug)]
+pub struct Input {
+    bindings: KeyBindings,
+    down: HashSet<Button>,
+    pressed: HashSet<Button>,
+    released: HashSet<Button>,
+    cursor: Option<(f64, f64)>,
+    cursor_delta: (f32, f32),
+    motion: (f32, f32),
+    scroll: f32,
+    focused: bool,
+}
+
+impl Input {
+    pub fn new(bindings: KeyBindings) -> Self {
+        Input {
+            bindings,
+            down: HashSet::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+            cursor: None,
+            cursor_delta: (0.0, 0.0),
+            motion: (0.0, 0.0),
+            scroll: 0.0,
+            focused: true,
+        }
+    }
+
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+    }
+
+    pub fn handle_event<T>(&mut self, event: &Event<T>) {
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            virtual_keycode: Some(key),
+                            state,
+                            ..
+                        },
+                    ..
+                } => self.set(Button::Key(*key), *state),
+                WindowEvent::MouseInput { button, state, .. } => {
+                    self.set(Button::Mouse(*button), *state)
+                }
+                WindowEvent::CursorMoved { position, .. } => {
+                    self.move_cursor(position.x, position.y)
+                }
+                WindowEvent::CursorLeft { .. } => self.cursor = None,
+                WindowEvent::MouseWheel { delta, .. } => match delta {
+                    MouseScrollDelta::LineDelta(_, lines) => self.add_scroll(*lines),
+                    MouseScrollDelta::PixelDelta(pixels) => {
+                        self.add_scroll(pixels.y as f32 / PIXELS_PER_LINE)
+                    }
+                },
+                WindowEvent::Focused(focused) => {
+                    self.focused = *focused;
+                    // Releases are not reported to an unfocused window.
+                    if !focused {
+                        for button in self.down.drain() {
+                            self.released.insert(button);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta: (dx, dy) },
+                ..
+            } if self.focused => self.add_motion(*dx as f32, *dy as f32),
+            _ => {}
+        }
+    }
+
+    fn set(&mut self, button: Button, state: ElementState) {
+        match state {
+            ElementState::Pressed => self.press(button),
+            ElementState::Released => self.release(button),
+        }
+    }
+
+    /// Records `button` going down. Repeated presses of a held key are
+    /// ignored.
+    pub fn press(&mut self, button: Button) {
+        if self.down.insert(button) {
+            self.pressed.insert(button);
+        }
+    }
+
+    pub fn release(&mut self, button: Button) {
+        if self.down.remove(&button) {
+            self.released.insert(button);
+        }
+    }
+
+    pub fn move_cursor(&mut self, x: f64, y: f64) {
+        if let Some((last_x, last_y)) = self.cursor {
+            self.cursor_delta.0 += (x - last_x) as f32;
+            self.cursor_delta.1 += (y - last_y) as f32;
+        }
+        self.cursor = Some((x, y));
+    }
+
+    /// Records raw mouse movement, which keeps being reported while the
+    /// cursor is grabbed.
+    pub fn add_motion(&mut self, dx: f32, dy: f32) {
+        self.motion.0 += dx;
+        self.motion.1 += dy;
+    }
+
+    /// Records scrolling by `lines` wheel notches, positive away from the user.
+    pub fn add_scroll(&mut self, lines: f32) {
+        self.scroll += lines;
+    }
+
+    /// Forgets what happened during the frame. Called by the harness after
+    /// every frame.
+    pub fn end_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.cursor_delta = (0.0, 0.0);
+        self.motion = (0.0, 0.0);
+        self.scroll = 0.0;
+    }
+
+    /// Whether `button` is held down.
+    pub fn is_down(&self, button: Button) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Whether any button at all is held down.
+    pub fn is_any_down(&self) -> bool {
+        !self.down.is_empty()
+    }
+
+    /// Whether `button` went down during this frame.
+    pub fn just_pressed(&self, button: Button) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Whether `button` was let go during this frame.
+    pub fn just_released(&self, button: Button) -> bool {
+        self.released.contains(&button)
+    }
+
+    /// Whether any button bound to `action` is held down.
+    pub fn is_action_down(&self, action: &str) -> bool {
+        self.any_bound(action, |button| self.is_down(button))
+    }
+
+    pub fn action_just_pressed(&self, action: &str) -> bool {
+        self.any_bound(action, |button| self.just_pressed(button))
+    }
+
+    pub fn action_just_released(&self, action: &str) -> bool {
+        self.any_bound(action, |button| self.just_released(button))
+    }
+
+    fn any_bound(&self, action: &str, test: impl Fn(Button) -> bool) -> bool {
+        self.bindings
+            .buttons(action)
+            .iter()
+            .any(|button| test(*button))
+    }
+
+    /// The cursor position in logical pixels, or `None` while it is outside
+    /// the window.
+    pub fn cursor(&self) -> Option<(f64, f64)> {
+        self.cursor
+    }
+
+    /// How far the cursor moved over the window during this frame.
+    pub fn cursor_delta(&self) -> (f32, f32) {
+        self.cursor_delta
+    }
+
+    /// How far the mouse moved during this frame, grabbed or not.
+    pub fn motion(&self) -> (f32, f32) {
+        self.motion
+    }
+
+    /// Wheel notches scrolled during this frame.
+    pub fn scroll(&self) -> f32 {
+        self.scroll
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Input::new(KeyBindings::default())
+    }
+}
@@ -0,0 +1,34 @@
+pub mod adapter;
+pub mod app;
+pub mod binding;
+pub mod camera;
+pub mod chapters;
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod error;
+pub mod frame;
+pub mod headless;
+pub mod input;
+pub mod math;
+pub mod projection;
+pub mod push_constants;
+pub mod shader;
+pub mod uniform;
+pub mod vertex;
+
+// Lets the derives name this crate the same way inside and outside of it.
+extern crate self as glium_tutorial_but_its_rendy;
+
+pub use glium_tutorial_but_its_rendy_derive::{Uniform, Vertex};
+
+pub use app::{init_logger, App, Chapter, Redraw};
+pub use camera::Camera;
+pub use clock::Clock;
+pub use config::RendyConfig;
+pub use error::Error;
+pub use frame::FrameContext;
+pub use headless::Headless;
+pub use input::Input;
+pub use push_constants::PushConstants;
+pub use uniform::{FrameUniforms, UniformBuffer};
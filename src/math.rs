@@ -0,0 +1,429 @@
+//! Just enough linear algebra for the tutorial.
+//!
+//! Matrices are column-major like GLSL's, so a `Mat4` can be copied into a
+//! `mat4` uniform as is. World and view space are right-handed with the
+//! camera looking down -Z. Projections produce Vulkan clip space: Y points
+//! down and depth goes from 0 at the near plane to 1 at the far plane.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::uniform::{BlockLayout, GlslType};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+    pub const ONE: Vec3 = Vec3::new(1.0, 1.0, 1.0);
+    pub const X: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+    pub const Y: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    pub const Z: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn dot(self, other: Vec3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Vec3 {
+        self * (1.0 / self.length())
+    }
+
+    pub fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: f32) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<[f32; 3]> for Vec3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl From<Vec3> for [f32; 3] {
+    fn from(v: Vec3) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Vec4 {
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Vec4 { x, y, z, w }
+    }
+
+    pub fn truncate(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+impl From<[f32; 4]> for Vec4 {
+    fn from([x, y, z, w]: [f32; 4]) -> Self {
+        Vec4::new(x, y, z, w)
+    }
+}
+
+/// A rotation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// A rotation of `angle` radians counter-clockwise around `axis`.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalize() * (angle * 0.5).sin();
+        Quat {
+            x: axis.x,
+            y: axis.y,
+            z: axis.z,
+            w: (angle * 0.5).cos(),
+        }
+    }
+
+    pub fn normalize(self) -> Quat {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        Quat {
+            x: self.x / length,
+            y: self.y / length,
+            z: self.z / length,
+            w: self.w / length,
+        }
+    }
+
+    pub fn conjugate(self) -> Quat {
+        Quat {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+
+    fn vector(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::IDENTITY
+    }
+}
+
+/// Composes rotations, `a * b` rotates by `b` first.
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, other: Quat) -> Quat {
+        let (a, b) = (self.vector(), other.vector());
+        let v = b * self.w + a * other.w + a.cross(b);
+        Quat {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: self.w * other.w - a.dot(b),
+        }
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let q = self.vector();
+        let t = q.cross(v) * 2.0;
+        v + t * self.w + q.cross(t)
+    }
+}
+
+/// A column-major 4x4 matrix.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        cols: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const fn from_cols(cols: [[f32; 4]; 4]) -> Self {
+        Mat4 { cols }
+    }
+
+    pub fn from_translation(v: Vec3) -> Self {
+        let mut m = Mat4::IDENTITY;
+        m.cols[3] = [v.x, v.y, v.z, 1.0];
+        m
+    }
+
+    pub fn from_scale(v: Vec3) -> Self {
+        let mut m = Mat4::IDENTITY;
+        m.cols[0][0] = v.x;
+        m.cols[1][1] = v.y;
+        m.cols[2][2] = v.z;
+        m
+    }
+
+    pub fn from_quat(q: Quat) -> Self {
+        let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+        Mat4::from_cols([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y + w * z),
+                2.0 * (x * z - w * y),
+                0.0,
+            ],
+            [
+                2.0 * (x * y - w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z + w * x),
+                0.0,
+            ],
+            [
+                2.0 * (x * z + w * y),
+                2.0 * (y * z - w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A rotation of `angle` radians counter-clockwise around the Z axis.
+    pub fn from_rotation_z(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Mat4::from_cols([
+            [cos, sin, 0.0, 0.0],
+            [-sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Translates by `v` before applying `self`.
+    pub fn translate(self, v: Vec3) -> Self {
+        self * Mat4::from_translation(v)
+    }
+
+    /// Rotates by `q` before applying `self`.
+    pub fn rotate(self, q: Quat) -> Self {
+        self * Mat4::from_quat(q)
+    }
+
+    /// Scales by `v` before applying `self`.
+    pub fn scale(self, v: Vec3) -> Self {
+        self * Mat4::from_scale(v)
+    }
+
+    /// A perspective projection with a vertical field of view of `fov_y`
+    /// radians.
+    pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fov_y * 0.5).tan();
+        Mat4::from_cols([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, -f, 0.0, 0.0],
+            [0.0, 0.0, far / (near - far), -1.0],
+            [0.0, 0.0, near * far / (near - far), 0.0],
+        ])
+    }
+
+    /// Maps the box between the given planes onto clip space, with `top`
+    /// at the top of the screen.
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        Mat4::from_cols([
+            [2.0 / (right - left), 0.0, 0.0, 0.0],
+            [0.0, -2.0 / (top - bottom), 0.0, 0.0],
+            [0.0, 0.0, 1.0 / (near - far), 0.0],
+            [
+                -(right + left) / (right - left),
+                (top + bottom) / (top - bottom),
+                near / (near - far),
+                1.0,
+            ],
+        ])
+    }
+
+    /// A view matrix for a camera at `eye` looking at `target`.
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let f = (target - eye).normalize();
+        let s = f.cross(up).normalize();
+        let u = s.cross(f);
+        Mat4::from_cols([
+            [s.x, u.x, -f.x, 0.0],
+            [s.y, u.y, -f.y, 0.0],
+            [s.z, u.z, -f.z, 0.0],
+            [-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0],
+        ])
+    }
+
+    pub fn transpose(self) -> Self {
+        let mut m = self;
+        for (c, col) in m.cols.iter_mut().enumerate() {
+            for (r, value) in col.iter_mut().enumerate() {
+                *value = self.cols[r][c];
+            }
+        }
+        m
+    }
+
+    /// Transforms a point, including the perspective divide.
+    pub fn transform_point(self, p: Vec3) -> Vec3 {
+        let v = self * p.extend(1.0);
+        v.truncate() * (1.0 / v.w)
+    }
+
+    /// Transforms a direction, ignoring translation.
+    pub fn transform_vector(self, v: Vec3) -> Vec3 {
+        (self * v.extend(0.0)).truncate()
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Mat4::IDENTITY
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    fn mul(self, v: Vec4) -> Vec4 {
+        let v = v.to_array();
+        let mut out = [0.0; 4];
+        for (r, out) in out.iter_mut().enumerate() {
+            *out = (0..4).map(|c| self.cols[c][r] * v[c]).sum();
+        }
+        Vec4::new(out[0], out[1], out[2], out[3])
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut cols = [[0.0; 4]; 4];
+        for (c, col) in cols.iter_mut().enumerate() {
+            let Vec4 { x, y, z, w } = self * Vec4::from(other.cols[c]);
+            *col = [x, y, z, w];
+        }
+        Mat4::from_cols(cols)
+    }
+}
+
+impl From<Mat4> for [[f32; 4]; 4] {
+    fn from(m: Mat4) -> Self {
+        m.cols
+    }
+}
+
+unsafe impl GlslType for Vec3 {
+    fn align(_: BlockLayout) -> u32 {
+        16
+    }
+
+    fn size(_: BlockLayout) -> u32 {
+        12
+    }
+}
+
+unsafe impl GlslType for Vec4 {
+    fn align(_: BlockLayout) -> u32 {
+        16
+    }
+
+    fn size(_: BlockLayout) -> u32 {
+        16
+    }
+}
+
+unsafe impl GlslType for Mat4 {
+    fn align(_: BlockLayout) -> u32 {
+        16
+    }
+
+    fn size(_: BlockLayout) -> u32 {
+        64
+    }
+}
@@ -0,0 +1,96 @@
+use rendy::{
+    command::RenderPassEncoder,
+    factory::Factory,
+    hal::{self, window::Extent2D},
+    resource::{DescriptorSetLayout, Handle},
+};
+
+use crate::{
+    error::Result,
+    math::{Mat4, Vec3},
+    shader::Shaders,
+    FrameUniforms, Uniform,
+};
+
+/// The uniform every chapter's vertex shader declares as
+///
+/// ```glsl
+/// layout(set = 1, binding = 0) uniform Projection {
+///     mat4 projection;
+///     mat4 view;
+/// };
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Uniform)]
+pub struct Projection {
+    pub projection: Mat4,
+    pub view: Mat4,
+}
+
+impl Projection {
+    /// The descriptor set index the projection is bound at.
+    pub const SET: u32 = 1;
+
+    /// Keeps the -1..1 square of clip space square on a framebuffer of the
+    /// given size, extending the view along its longer axis.
+    pub fn for_extent(extent: Extent2D) -> Self {
+        let (width, height) = (extent.width.max(1) as f32, extent.height.max(1) as f32);
+        let scale = if width > height {
+            Vec3::new(height / width, 1.0, 1.0)
+        } else {
+            Vec3::new(1.0, width / height, 1.0)
+        };
+
+        Projection {
+            projection: Mat4::from_scale(scale),
+            view: Mat4::IDENTITY,
+        }
+    }
+}
+
+/// The projection uniform of every frame in flight and the descriptor sets
+/// they are bound in.
+#[derive(Debug)]
+pub struct ProjectionSet<B: hal::Backend> {
+    uniforms: FrameUniforms<B, Projection>,
+}
+
+impl<B: hal::Backend> ProjectionSet<B> {
+    pub fn new(
+        factory: &Factory<B>,
+        shaders: &Shaders,
+        layout: &Handle<DescriptorSetLayout<B>>,
+        frames: usize,
+    ) -> Result<Self> {
+        let uniforms = FrameUniforms::new(factory, frames, shaders.block("Projection")?, layout)?;
+        uniforms.bind(
+            factory,
+            &shaders.bindings,
+            Projection::SET,
+            "Projection",
+            Ok,
+        )?;
+
+        Ok(ProjectionSet { uniforms })
+    }
+
+    pub fn write(&mut self, factory: &Factory<B>, frame: usize, value: &Projection) -> Result<()> {
+        self.uniforms.write(factory, frame, value)
+    }
+
+    pub fn bind(
+        &self,
+        encoder: &mut RenderPassEncoder<'_, B>,
+        layout: &B::PipelineLayout,
+        frame: usize,
+    ) {
+        unsafe {
+            encoder.bind_graphics_descriptor_sets(
+                layout,
+                Projection::SET,
+                std::iter::once(self.uniforms.descriptor_set(frame)),
+                std::iter::empty(),
+            );
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use rendy::{
+    command::RenderPassEncoder,
+    hal::{self, pso::ShaderStageFlags},
+};
+
+use crate::{
+    binding::PushConstantRange,
+    error::{Error, Result},
+    uniform::{self, Uniform},
+};
+
+/// The smallest `maxPushConstantsSize` Vulkan allows, so every device
+/// supports at least this many bytes.
+pub const MAX_PUSH_CONSTANTS_SIZE: u32 = 128;
+
+/// Pushes values of a `#[repr(C)]` type as push constants.
+///
+/// `T` is described with `#[derive(Uniform)]`, using `#[uniform(std430)]` to
+/// match how GLSL lays out push constant blocks.
+#[derive(Debug)]
+pub struct PushConstants<T> {
+    stages: ShaderStageFlags,
+    offset: u32,
+    marker: PhantomData<T>,
+}
+
+impl<T: Uniform> PushConstants<T> {
+    /// Push constants at offset 0, read by the given stages.
+    pub fn new(stages: ShaderStageFlags) -> Result<Self> {
+        Self::with_offset(stages, 0)
+    }
+
+    pub fn with_offset(stages: ShaderStageFlags, offset: u32) -> Result<Self> {
+        let size = Self::size();
+
+        if offset % 4 != 0 {
+            return Err(Error::PushConstants(format!(
+                "offset {} is not a multiple of 4",
+                offset
+            )));
+        }
+
+        if offset + size > MAX_PUSH_CONSTANTS_SIZE {
+            return Err(Error::PushConstants(format!(
+                "{} is {} bytes at offset {}, only {} bytes are guaranteed to be available",
+                std::any::type_name::<T>(),
+                size,
+                offset,
+                MAX_PUSH_CONSTANTS_SIZE,
+            )));
+        }
+
+        Ok(PushConstants {
+            stages,
+            offset,
+            marker: PhantomData,
+        })
+    }
+
+    /// Push constants for a `reflected` block, checking `T` matches it.
+    pub fn reflected(reflected: &PushConstantRange) -> Result<Self> {
+        uniform::check::<T>(&reflected.block)?;
+
+        let size = Self::size();
+        let reflected_size = reflected.range.end - reflected.range.start;
+        if size != reflected_size {
+            return Err(Error::PushConstants(format!(
+                "{} is {} bytes, block `{}` covers {}",
+                std::any::type_name::<T>(),
+                size,
+                reflected.block.name,
+                reflected_size,
+            )));
+        }
+
+        Self::with_offset(reflected.stages, reflected.range.start)
+    }
+
+    /// Size of `T` rounded up to whole words.
+    fn size() -> u32 {
+        (std::mem::size_of::<T>() as u32 + 3) / 4 * 4
+    }
+
+    pub fn stages(&self) -> ShaderStageFlags {
+        self.stages
+    }
+
+    pub fn range(&self) -> std::ops::Range<u32> {
+        self.offset..self.offset + Self::size()
+    }
+
+    /// Serializes `value` into the words `push_constants` expects.
+    pub fn words(value: &T) -> Vec<u32> {
+        let mut words = vec![0u32; Self::size() as usize / 4];
+        // Sound because `T` is plain data and `words` holds at least
+        // `size_of::<T>()` bytes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                value as *const T as *const u8,
+                words.as_mut_ptr() as *mut u8,
+                std::mem::size_of::<T>(),
+            );
+        }
+        words
+    }
+
+    pub fn push<B: hal::Backend>(
+        &self,
+        encoder: &mut RenderPassEncoder<'_, B>,
+        layout: &B::PipelineLayout,
+        value: &T,
+    ) {
+        unsafe {
+            encoder.push_constants(layout, self.stages, self.offset, &Self::words(value));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
+
+use rendy::{
+    core::types::vertex::{AsVertex, VertexFormat},
+    factory::Factory,
+    graph::render::Layout,
+    hal::{self, pso::ShaderStageFlags},
+    shader::{Shader as _, ShaderSet, ShaderSetBuilder, SpirvReflection, SpirvShader},
+};
+
+use crate::{
+    binding::{Bindings, Block},
+    error::{Error, Result},
+    push_constants::PushConstants,
+    uniform::{self, Uniform, UniformBuffer},
+    vertex,
+};
+
+/// A shader compiled to SPIR-V by the build script, along with the GLSL file
+/// it came from.
+#[derive(Clone, Copy, Debug)]
+pub struct ShaderSource {
+    pub path: &'static str,
+    pub spirv: &'static [u8],
+    pub stage: ShaderStageFlags,
+}
+
+/// Whether shaders are recompiled from their GLSL files instead of using the
+/// SPIR-V baked into the binary. Set while hot reloading.
+static LOAD_FROM_DISK: AtomicBool = AtomicBool::new(false);
+
+/// Switches to compiling shaders from disk. Returns whether that is possible,
+/// which requires the `shader-compiler` feature.
+pub fn set_load_from_disk(load: bool) -> bool {
+    if load && !cfg!(feature = "shader-compiler") {
+        log::warn!("Built without the `shader-compiler` feature, shaders can't be reloaded");
+        return false;
+    }
+    LOAD_FROM_DISK.store(load, Ordering::Relaxed);
+    load
+}
+
+impl ShaderSource {
+    pub fn spirv(&self) -> Result<SpirvShader> {
+        #[cfg(feature = "shader-compiler")]
+        {
+            if LOAD_FROM_DISK.load(Ordering::Relaxed) {
+                return self.compile();
+            }
+        }
+
+        SpirvShader::from_bytes(self.spirv, self.stage, "main").map_err(|err| {
+            Error::ShaderCompile {
+                path: self.path.into(),
+                message: format!("{:?}", err),
+            }
+        })
+    }
+
+    /// Compiles the GLSL file with shaderc.
+    #[cfg(feature = "shader-compiler")]
+    fn compile(&self) -> Result<SpirvShader> {
+        use rendy::shader::{ShaderKind, SourceCodeShaderInfo, SourceLanguage};
+
+        let source = std::fs::read_to_string(self.path).map_err(|cause| Error::Asset {
+            path: self.path.into(),
+            cause,
+        })?;
+
+        let kind = match self.stage {
+            ShaderStageFlags::VERTEX => ShaderKind::Vertex,
+            ShaderStageFlags::FRAGMENT => ShaderKind::Fragment,
+            stage => {
+                return Err(Error::ShaderCompile {
+                    path: self.path.into(),
+                    message: format!("unsupported shader stage {:?}", stage),
+                })
+            }
+        };
+
+        SourceCodeShaderInfo::new(source, self.path, kind, SourceLanguage::GLSL, "main")
+            .precompile()
+            .map_err(|err| Error::ShaderCompile {
+                path: self.path.into(),
+                message: format!("{:?}", err),
+            })
+    }
+}
+
+/// A compiled vertex and fragment shader pair and its reflection.
+#[derive(Clone, Debug)]
+pub struct Shaders {
+    pub set: ShaderSetBuilder,
+    pub reflection: SpirvReflection,
+    /// The vertex attributes the vertex shader consumes.
+    pub vertex: VertexFormat,
+    pub bindings: Bindings,
+}
+
+impl Shaders {
+    pub fn compile(vertex: &ShaderSource, fragment: &ShaderSource) -> Result<Self> {
+        let vertex_spirv = vertex.spirv()?;
+        let fragment_spirv = fragment.spirv()?;
+
+        let set = ShaderSetBuilder::default()
+            .with_vertex(&vertex_spirv)
+            .map_err(|err| Error::ShaderCompile {
+                path: vertex.path.into(),
+                message: format!("{:?}", err),
+            })?
+            .with_fragment(&fragment_spirv)
+            .map_err(|err| Error::ShaderCompile {
+                path: fragment.path.into(),
+                message: format!("{:?}", err),
+            })?;
+
+        let reflection = set.reflect().map_err(reflection_error)?;
+        let vertex = reflection.attributes_range(..).map_err(reflection_error)?;
+
+        let bindings = Bindings::reflect(&[
+            (
+                &vertex_spirv.spirv().map_err(reflection_error)?,
+                vertex_spirv.stage(),
+            ),
+            (
+                &fragment_spirv.spirv().map_err(reflection_error)?,
+                fragment_spirv.stage(),
+            ),
+        ])?;
+
+        Ok(Shaders {
+            set,
+            reflection,
+            vertex,
+            bindings,
+        })
+    }
+
+    /// Checks that `V` matches the inputs of the vertex shader.
+    pub fn check_vertex<V: AsVertex + vertex::VertexFields>(&self) -> Result<()> {
+        vertex::check::<V>(&self.vertex)
+    }
+
+    /// The layout of the uniform block GLSL calls `name`.
+    pub fn block(&self, name: &str) -> Result<&Block> {
+        self.bindings
+            .get(name)?
+            .block
+            .as_ref()
+            .ok_or_else(|| Error::Binding {
+                name: name.into(),
+                message: "not a uniform or storage block".into(),
+            })
+    }
+
+    /// Checks that `U` matches the uniform block GLSL calls `name`.
+    pub fn check_uniform<U: Uniform>(&self, name: &str) -> Result<()> {
+        uniform::check::<U>(self.block(name)?)
+    }
+
+    /// Creates a buffer for the uniform block GLSL calls `name`.
+    pub fn uniform_buffer<B, U>(
+        &self,
+        factory: &Factory<B>,
+        name: &str,
+    ) -> Result<UniformBuffer<B, U>>
+    where
+        B: hal::Backend,
+        U: Uniform,
+    {
+        UniformBuffer::new(factory, self.block(name)?)
+    }
+
+    /// Push constants for the block GLSL calls `name`.
+    pub fn push_constants<T: Uniform>(&self, name: &str) -> Result<PushConstants<T>> {
+        PushConstants::reflected(self.bindings.push_constants(name)?)
+    }
+
+    /// The pipeline layout, built from the reflected descriptor bindings.
+    pub fn layout(&self) -> Layout {
+        self.bindings.layout()
+    }
+
+    pub fn vertices(
+        &self,
+    ) -> Vec<(
+        Vec<hal::pso::Element<hal::format::Format>>,
+        hal::pso::ElemStride,
+        hal::pso::VertexInputRate,
+    )> {
+        vec![self
+            .vertex
+            .gfx_vertex_input_desc(hal::pso::VertexInputRate::Vertex)]
+    }
+
+    pub fn build<B: hal::Backend>(&self, factory: &mut Factory<B>) -> ShaderSet<B> {
+        self.set.build(factory, Default::default()).unwrap()
+    }
+}
+
+fn reflection_error<E: std::fmt::Debug>(err: E) -> Error {
+    Error::Reflection(format!("{:?}", err))
+}
+
+/// Polls the modification times of a set of shader sources.
+#[derive(Debug)]
+pub struct ShaderWatcher {
+    files: Vec<(&'static str, Option<SystemTime>)>,
+    last_poll: Instant,
+}
+
+impl ShaderWatcher {
+    /// How often the files are checked at most.
+    pub const INTERVAL: Duration = Duration::from_millis(250);
+
+    pub fn new(sources: &[ShaderSource]) -> Self {
+        ShaderWatcher {
+            files: sources
+                .iter()
+                .map(|source| (source.path, modified(source.path)))
+                .collect(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Returns true if any of the files changed since the previous poll.
+    pub fn poll(&mut self) -> bool {
+        if self.last_poll.elapsed() < Self::INTERVAL {
+            return false;
+        }
+        self.last_poll = Instant::now();
+
+        let mut changed = false;
+        for (path, last) in &mut self.files {
+            let current = modified(*path);
+            if current != *last {
+                log::debug!("{} changed", path);
+                *last = current;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: impl AsRef<Path>) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
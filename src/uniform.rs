@@ -0,0 +1,379 @@
+use std::marker::PhantomData;
+
+use rendy::{
+    factory::Factory,
+    hal,
+    memory::Dynamic,
+    resource::{Buffer, BufferInfo, DescriptorSet, DescriptorSetLayout, Escape, Handle},
+};
+
+use crate::{
+    binding::{Binder, Bindings, Block},
+    error::{Error, Result},
+};
+
+/// The GLSL rules a block's members are laid out by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockLayout {
+    /// The default for uniform blocks.
+    Std140,
+    /// The default for storage blocks.
+    Std430,
+}
+
+/// A Rust type with a GLSL counterpart of known alignment and size.
+///
+/// Arrays of two to four scalars are vectors and `[[f32; 4]; 4]` is a `mat4`.
+/// GLSL arrays are `GlslArray`s, and `#[derive(Uniform)]` structs can be
+/// nested in other uniforms.
+///
+/// # Safety
+///
+/// The type must be plain data whose every bit pattern is valid, with the
+/// alignment and size its GLSL counterpart has under each layout.
+pub unsafe trait GlslType: Copy + 'static {
+    fn align(layout: BlockLayout) -> u32;
+    fn size(layout: BlockLayout) -> u32;
+}
+
+macro_rules! glsl_scalars {
+    ($($scalar:ty),*) => {$(
+        unsafe impl GlslType for $scalar {
+            fn align(_: BlockLayout) -> u32 { 4 }
+            fn size(_: BlockLayout) -> u32 { 4 }
+        }
+
+        unsafe impl GlslType for [$scalar; 2] {
+            fn align(_: BlockLayout) -> u32 { 8 }
+            fn size(_: BlockLayout) -> u32 { 8 }
+        }
+
+        unsafe impl GlslType for [$scalar; 3] {
+            fn align(_: BlockLayout) -> u32 { 16 }
+            fn size(_: BlockLayout) -> u32 { 12 }
+        }
+
+        unsafe impl GlslType for [$scalar; 4] {
+            fn align(_: BlockLayout) -> u32 { 16 }
+            fn size(_: BlockLayout) -> u32 { 16 }
+        }
+    )*};
+}
+
+glsl_scalars!(f32, i32, u32);
+
+unsafe impl GlslType for [[f32; 4]; 4] {
+    fn align(_: BlockLayout) -> u32 {
+        16
+    }
+
+    fn size(_: BlockLayout) -> u32 {
+        64
+    }
+}
+
+/// A GLSL array, `GlslArray<[[f32; 4]; 3]>` for `vec4 colors[3]`.
+///
+/// Rust arrays of scalars already stand for vectors, so GLSL arrays need
+/// their own type. Under `std140` the element stride is rounded up to 16
+/// bytes, so `float weights[3]` takes 48 bytes and is best declared as
+/// `GlslArray<[[f32; 4]; 3]>`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlslArray<A>(pub A);
+
+macro_rules! glsl_arrays {
+    ($($len:expr),*) => {$(
+        unsafe impl<T: GlslType> GlslType for GlslArray<[T; $len]> {
+            fn align(layout: BlockLayout) -> u32 {
+                array_align::<T>(layout)
+            }
+
+            fn size(layout: BlockLayout) -> u32 {
+                $len * round_up(T::size(layout), array_align::<T>(layout))
+            }
+        }
+    )*};
+}
+
+glsl_arrays!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32
+);
+
+fn array_align<T: GlslType>(layout: BlockLayout) -> u32 {
+    match layout {
+        BlockLayout::Std140 => round_up(T::align(layout), 16),
+        BlockLayout::Std430 => T::align(layout),
+    }
+}
+
+/// The alignment of a struct with `members` nested in a `layout` block.
+///
+/// The members keep the offsets of their own struct's layout, only the
+/// struct's alignment and size follow the enclosing block.
+pub fn struct_align(members: &[UniformMember], layout: BlockLayout) -> u32 {
+    let align = members.iter().map(|member| member.align).max().unwrap_or(1);
+    match layout {
+        BlockLayout::Std140 => round_up(align, 16),
+        BlockLayout::Std430 => align,
+    }
+}
+
+/// The size of a struct with `members` nested in a `layout` block, which
+/// includes the padding up to its alignment.
+pub fn struct_size(members: &[UniformMember], layout: BlockLayout) -> u32 {
+    round_up(end_of(members), struct_align(members, layout))
+}
+
+fn end_of(members: &[UniformMember]) -> u32 {
+    members
+        .iter()
+        .map(|member| member.offset + member.size)
+        .max()
+        .unwrap_or(0)
+}
+
+/// A member of a Rust uniform type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniformMember {
+    pub name: &'static str,
+    /// Where the member actually is in the Rust type.
+    pub offset: u32,
+    pub align: u32,
+    pub size: u32,
+}
+
+/// A `#[repr(C)]` struct that can be copied into a GLSL block.
+///
+/// Implement this with `#[derive(Uniform)]`, which picks `std140` unless the
+/// struct is marked `#[uniform(std430)]`.
+///
+/// # Safety
+///
+/// The type must be `#[repr(C)]` plain data and `members` must describe its
+/// fields as they are laid out in memory.
+pub unsafe trait Uniform: Copy + 'static {
+    const LAYOUT: BlockLayout;
+
+    /// Every member, in declaration order.
+    fn members() -> Vec<UniformMember>;
+}
+
+/// Describes where `U` deviates from its own block layout rules and from the
+/// `reflected` block.
+pub fn mismatches<U: Uniform>(reflected: &Block) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let members = U::members();
+
+    // Where the layout rules place each member, independently of the shader.
+    let mut end = 0;
+    for member in &members {
+        let expected = round_up(end, member.align);
+        if member.offset != expected {
+            mismatches.push(format!(
+                "`{}` is at offset {}, {:?} places it at {}",
+                member.name,
+                member.offset,
+                U::LAYOUT,
+                expected,
+            ));
+        }
+        end = member.offset + member.size;
+    }
+
+    let size = std::mem::size_of::<U>() as u32;
+    if size < end_of(&members) {
+        mismatches.push(format!(
+            "is {} bytes, {:?} needs {} for its members",
+            size,
+            U::LAYOUT,
+            end_of(&members),
+        ));
+    }
+    // The reflected size may stop at the last member or include the padding
+    // up to the block's alignment, and `U` may do either.
+    let members_end = reflected
+        .members
+        .iter()
+        .map(|member| member.offset + member.size)
+        .max()
+        .unwrap_or(0);
+    let padded = round_up(reflected.size, struct_align(&members, U::LAYOUT));
+    if size < members_end || size > padded {
+        mismatches.push(format!(
+            "is {} bytes, block `{}` is {}",
+            size, reflected.name, reflected.size,
+        ));
+    }
+
+    if members.len() != reflected.members.len() {
+        mismatches.push(format!(
+            "declares {} members, block `{}` has {}",
+            members.len(),
+            reflected.name,
+            reflected.members.len(),
+        ));
+    }
+
+    for (member, glsl) in members.iter().zip(reflected.members.iter()) {
+        if member.offset != glsl.offset {
+            mismatches.push(format!(
+                "`{}` is at offset {}, the shader expects `{}` at {}",
+                member.name, member.offset, glsl.name, glsl.offset,
+            ));
+        }
+        if member.size != glsl.size {
+            mismatches.push(format!(
+                "`{}` is {} bytes, the shader expects `{}` to be {}",
+                member.name, member.size, glsl.name, glsl.size,
+            ));
+        }
+    }
+
+    mismatches
+}
+
+/// Checks that `U` can be written to a buffer bound to the `reflected` block.
+pub fn check<U: Uniform>(reflected: &Block) -> Result<()> {
+    let mismatches = mismatches::<U>(reflected);
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UniformLayout {
+            uniform: std::any::type_name::<U>(),
+            details: mismatches.join("\n"),
+        })
+    }
+}
+
+fn round_up(offset: u32, align: u32) -> u32 {
+    (offset + align - 1) / align * align
+}
+
+/// A host visible buffer holding a single `U`.
+#[derive(Debug)]
+pub struct UniformBuffer<B: hal::Backend, U> {
+    buffer: Escape<Buffer<B>>,
+    size: u64,
+    marker: PhantomData<U>,
+}
+
+impl<B, U> UniformBuffer<B, U>
+where
+    B: hal::Backend,
+    U: Uniform,
+{
+    /// Creates a buffer for the `reflected` block after checking `U` matches it.
+    pub fn new(factory: &Factory<B>, reflected: &Block) -> Result<Self> {
+        check::<U>(reflected)?;
+
+        // The reflected size may include trailing padding `U` doesn't have.
+        let size = (std::mem::size_of::<U>() as u64).max(reflected.size as u64);
+        let buffer = factory
+            .create_buffer(
+                BufferInfo {
+                    size,
+                    usage: hal::buffer::Usage::UNIFORM,
+                },
+                Dynamic,
+            )
+            .map_err(Error::resource("uniform buffer"))?;
+
+        Ok(UniformBuffer {
+            buffer,
+            size,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn write(&mut self, factory: &Factory<B>, value: &U) -> Result<()> {
+        // Sound because `U` is plain data laid out to match the block, and the
+        // buffer is host visible and large enough to hold it.
+        unsafe {
+            factory
+                .upload_visible_buffer(&mut self.buffer, 0, std::slice::from_ref(value))
+                .map_err(Error::resource("uniform data"))
+        }
+    }
+
+    pub fn raw(&self) -> &B::Buffer {
+        self.buffer.raw()
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+type Frame<B, U> = (UniformBuffer<B, U>, Escape<DescriptorSet<B>>);
+
+/// A uniform buffer and descriptor set for every frame in flight.
+///
+/// A frame only writes the buffer its own descriptor set points at, so it
+/// never overwrites data an earlier frame still being rendered reads.
+#[derive(Debug)]
+pub struct FrameUniforms<B: hal::Backend, U> {
+    frames: Vec<Frame<B, U>>,
+}
+
+impl<B, U> FrameUniforms<B, U>
+where
+    B: hal::Backend,
+    U: Uniform,
+{
+    /// Creates `frames` buffers for the `reflected` block, each with a
+    /// descriptor set of the given layout.
+    pub fn new(
+        factory: &Factory<B>,
+        frames: usize,
+        reflected: &Block,
+        layout: &Handle<DescriptorSetLayout<B>>,
+    ) -> Result<Self> {
+        let frames = (0..frames)
+            .map(|_| {
+                let uniform = UniformBuffer::new(factory, reflected)?;
+                let set = factory
+                    .create_descriptor_set(layout.clone())
+                    .map_err(Error::resource("descriptor set"))?;
+                Ok((uniform, set))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(FrameUniforms { frames })
+    }
+
+    /// Binds each frame's buffer as `name` in its descriptor set for set
+    /// index `index`. `extra` binds the resources shared by every frame.
+    pub fn bind<'a, F>(
+        &'a self,
+        factory: &Factory<B>,
+        bindings: &'a Bindings,
+        index: u32,
+        name: &str,
+        extra: F,
+    ) -> Result<()>
+    where
+        F: Fn(Binder<'a, B>) -> Result<Binder<'a, B>>,
+    {
+        for (uniform, set) in &self.frames {
+            bindings
+                .binder(index, set)
+                .uniform(name, uniform)
+                .and_then(&extra)?
+                .write(factory)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffer of frame `frame`.
+    pub fn write(&mut self, factory: &Factory<B>, frame: usize, value: &U) -> Result<()> {
+        self.frames[frame].0.write(factory, value)
+    }
+
+    /// The descriptor set of frame `frame`.
+    pub fn descriptor_set(&self, frame: usize) -> &B::DescriptorSet {
+        self.frames[frame].1.raw()
+    }
+}
@@ -0,0 +1,165 @@
+//! Checks vertex types against the inputs of their vertex shaders.
+//!
+//! `#[derive(Vertex)]` infers the format of `f32`, `u32` and `i32` fields and
+//! arrays of up to four of them:
+//!
+//! ```
+//! use glium_tutorial_but_its_rendy::Vertex;
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+//! struct Supported {
+//!     position: [f32; 2],
+//!     #[vertex(format = "Rgba8Unorm")]
+//!     color: [u8; 4],
+//! }
+//! ```
+//!
+//! Any other field needs a `#[vertex(format = "...")]`:
+//!
+//! ```compile_fail
+//! use glium_tutorial_but_its_rendy::Vertex;
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+//! struct Unsupported {
+//!     color: [u8; 4],
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use glium_tutorial_but_its_rendy::Vertex;
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+//! struct TooLong {
+//!     weights: [f32; 5],
+//! }
+//! ```
+//!
+//! and the struct has to be `#[repr(C)]`:
+//!
+//! ```compile_fail
+//! use glium_tutorial_but_its_rendy::Vertex;
+//!
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+//! struct NotReprC {
+//!     position: [f32; 2],
+//! }
+//! ```
+
+use rendy::{
+    core::types::vertex::{AsVertex, VertexFormat},
+    hal,
+};
+
+use crate::error::Error;
+
+/// The offset of every field of a vertex type, in declaration order.
+/// Implemented by `#[derive(Vertex)]`.
+pub trait VertexFields {
+    fn field_offsets() -> Vec<u32>;
+}
+
+/// A single vertex attribute, as declared in Rust or reflected from SPIR-V.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeDesc {
+    pub name: String,
+    pub format: hal::format::Format,
+    pub offset: u32,
+}
+
+pub fn attributes(format: &VertexFormat) -> Vec<AttributeDesc> {
+    format
+        .attributes
+        .iter()
+        .map(|attribute| AttributeDesc {
+            name: attribute.name().to_string(),
+            format: attribute.element().format,
+            offset: attribute.element().offset,
+        })
+        .collect()
+}
+
+/// Describes every difference between the vertex format declared in Rust and
+/// the one reflected from the vertex shader. Attributes are matched by position.
+pub fn mismatches(declared: &VertexFormat, reflected: &VertexFormat) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    let declared_attributes = attributes(declared);
+    let reflected_attributes = attributes(reflected);
+
+    if declared_attributes.len() != reflected_attributes.len() {
+        mismatches.push(format!(
+            "declares {} attributes, the shader consumes {}",
+            declared_attributes.len(),
+            reflected_attributes.len(),
+        ));
+    }
+
+    for (index, (declared, reflected)) in declared_attributes
+        .iter()
+        .zip(reflected_attributes.iter())
+        .enumerate()
+    {
+        if declared.name != reflected.name {
+            mismatches.push(format!(
+                "attribute {} is named `{}`, the shader calls it `{}`",
+                index, declared.name, reflected.name,
+            ));
+        }
+        if declared.format != reflected.format {
+            mismatches.push(format!(
+                "attribute {} (`{}`) is {:?}, the shader expects {:?}",
+                index, declared.name, declared.format, reflected.format,
+            ));
+        }
+        if declared.offset != reflected.offset {
+            mismatches.push(format!(
+                "attribute {} (`{}`) is at offset {}, the shader expects {}",
+                index, declared.name, declared.offset, reflected.offset,
+            ));
+        }
+    }
+
+    if declared.stride != reflected.stride {
+        mismatches.push(format!(
+            "stride is {}, the shader expects {}",
+            declared.stride, reflected.stride,
+        ));
+    }
+
+    mismatches
+}
+
+/// Checks that `V` can be fed to a vertex shader with the `reflected` inputs.
+pub fn check<V: AsVertex + VertexFields>(reflected: &VertexFormat) -> Result<(), Error> {
+    let declared = V::vertex();
+    let mut mismatches = mismatches(&declared, reflected);
+
+    for (attribute, offset) in attributes(&declared).iter().zip(V::field_offsets()) {
+        if attribute.offset != offset {
+            mismatches.push(format!(
+                "attribute `{}` is at offset {}, but padding puts its field at offset {}",
+                attribute.name, attribute.offset, offset,
+            ));
+        }
+    }
+
+    let size = std::mem::size_of::<V>() as u32;
+    if size != declared.stride {
+        mismatches.push(format!(
+            "is {} bytes, but its attributes only cover {}",
+            size, declared.stride,
+        ));
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::VertexLayout {
+            vertex: std::any::type_name::<V>(),
+            details: mismatches.join("\n"),
+        })
+    }
+}
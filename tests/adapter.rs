@@ -0,0 +1,110 @@
+//! Checks how adapters are selected from the configuration. None of this
+//! needs a GPU.
+
+use glium_tutorial_but_its_rendy::{
+    adapter::AdapterSelector,
+    config::{DeviceConfig, DeviceType},
+    RendyConfig,
+};
+use rendy::hal::adapter::{self, AdapterInfo};
+
+fn adapters() -> Vec<AdapterInfo> {
+    let info = |name: &str, device_type| AdapterInfo {
+        name: name.into(),
+        vendor: 0,
+        device: 0,
+        device_type,
+    };
+    vec![
+        info(
+            "Intel(R) UHD Graphics 630",
+            adapter::DeviceType::IntegratedGpu,
+        ),
+        info("NVIDIA GeForce GTX 1070", adapter::DeviceType::DiscreteGpu),
+        info("llvmpipe (LLVM 9.0, 256 bits)", adapter::DeviceType::Cpu),
+    ]
+}
+
+fn select(device: DeviceConfig) -> Result<Option<usize>, String> {
+    device.select(&adapters())
+}
+
+#[test]
+fn selectors_parse_from_the_command_line() {
+    assert_eq!(AdapterSelector::from("1"), AdapterSelector::Index(1));
+    assert_eq!(
+        AdapterSelector::from("geforce"),
+        AdapterSelector::Name("geforce".into())
+    );
+}
+
+#[test]
+fn selects_by_index() {
+    let adapters = adapters();
+    assert_eq!(AdapterSelector::Index(2).find(&adapters), Some(2));
+    assert_eq!(AdapterSelector::Index(3).find(&adapters), None);
+}
+
+#[test]
+fn selects_by_name_ignoring_case() {
+    let adapters = adapters();
+    assert_eq!(AdapterSelector::from("geforce").find(&adapters), Some(1));
+    assert_eq!(AdapterSelector::from("LLVMPIPE").find(&adapters), Some(2));
+    assert_eq!(AdapterSelector::from("radeon").find(&adapters), None);
+}
+
+#[test]
+fn adapter_takes_precedence_over_type() {
+    let device = DeviceConfig {
+        adapter: Some(AdapterSelector::from("intel")),
+        device_type: Some(DeviceType::Discrete),
+    };
+    assert_eq!(select(device), Ok(Some(0)));
+}
+
+#[test]
+fn missing_adapter_is_an_error() {
+    let device = DeviceConfig {
+        adapter: Some(AdapterSelector::from("radeon")),
+        device_type: Some(DeviceType::Cpu),
+    };
+    assert_eq!(
+        select(device),
+        Err("There is no adapter matching `radeon`".to_string())
+    );
+
+    let device = DeviceConfig {
+        adapter: Some(AdapterSelector::Index(3)),
+        device_type: None,
+    };
+    assert!(select(device).is_err());
+}
+
+#[test]
+fn falls_back_to_type_then_to_rendy() {
+    let device = DeviceConfig {
+        adapter: None,
+        device_type: Some(DeviceType::Cpu),
+    };
+    assert_eq!(select(device), Ok(Some(2)));
+
+    let device = DeviceConfig {
+        adapter: None,
+        device_type: Some(DeviceType::Virtual),
+    };
+    assert_eq!(select(device), Ok(None));
+    assert_eq!(select(DeviceConfig::default()), Ok(None));
+}
+
+#[test]
+fn adapter_is_read_from_the_config() {
+    let config = RendyConfig::parse("[device]\nadapter = 1", &[]).unwrap();
+    assert_eq!(config.device.adapter, Some(AdapterSelector::Index(1)));
+
+    let overrides = ["device.adapter=nvidia".to_string()];
+    let config = RendyConfig::parse("", &overrides).unwrap();
+    assert_eq!(
+        config.device.adapter,
+        Some(AdapterSelector::Name("nvidia".into()))
+    );
+}
@@ -0,0 +1,149 @@
+//! Checks the descriptor bindings reflected from the chapter shaders. None of
+//! this needs a GPU.
+
+use glium_tutorial_but_its_rendy::{chapters::shaders::*, shader::Shaders, Error};
+use rendy::hal::pso::{DescriptorType, ShaderStageFlags};
+
+mod shaders {
+    // Every test shader is included, but only some are used here.
+    #![allow(dead_code)]
+
+    use glium_tutorial_but_its_rendy::shader::ShaderSource;
+    use rendy::hal::pso::ShaderStageFlags;
+
+    include!(concat!(env!("OUT_DIR"), "/test_shaders.rs"));
+}
+
+fn binding_shaders() -> Shaders {
+    Shaders::compile(&shaders::VERTEX_BINDINGS, &shaders::FRAGMENT_BINDINGS).unwrap()
+}
+
+fn bind_error(result: Result<u32, Error>) -> (String, String) {
+    match result {
+        Err(Error::Binding { name, message }) => (name, message),
+        result => panic!("Expected a binding error, got {:?}", result),
+    }
+}
+
+#[test]
+fn separate_texture_and_sampler_are_reflected() {
+    let shaders = Shaders::compile(&VERTEX_06, &FRAGMENT_06).unwrap();
+
+    let locals = shaders.bindings.get("Locals").unwrap();
+    assert_eq!(locals.ty, DescriptorType::UniformBuffer);
+    assert_eq!(locals.stages, ShaderStageFlags::VERTEX);
+    assert_eq!(locals.block.as_ref().unwrap().size, 64);
+
+    let colormap = shaders.bindings.get("colormap").unwrap();
+    assert_eq!(colormap.ty, DescriptorType::SampledImage);
+    assert_eq!(colormap.stages, ShaderStageFlags::FRAGMENT);
+
+    let colorsampler = shaders.bindings.get("colorsampler").unwrap();
+    assert_eq!(colorsampler.ty, DescriptorType::Sampler);
+    assert_eq!(colorsampler.stages, ShaderStageFlags::FRAGMENT);
+}
+
+#[test]
+fn layout_covers_every_binding() {
+    let shaders = Shaders::compile(&VERTEX_06, &FRAGMENT_06).unwrap();
+    let layout = shaders.layout();
+
+    assert_eq!(layout.sets.len(), 2);
+    let bindings: Vec<_> = layout.sets[0]
+        .bindings
+        .iter()
+        .map(|binding| (binding.binding, binding.ty))
+        .collect();
+    assert_eq!(
+        bindings,
+        vec![
+            (0, DescriptorType::UniformBuffer),
+            (1, DescriptorType::SampledImage),
+            (2, DescriptorType::Sampler),
+        ]
+    );
+
+    let projection: Vec<_> = layout.sets[1]
+        .bindings
+        .iter()
+        .map(|binding| (binding.binding, binding.ty))
+        .collect();
+    assert_eq!(projection, vec![(0, DescriptorType::UniformBuffer)]);
+}
+
+#[test]
+fn unknown_name_is_reported() {
+    let shaders = Shaders::compile(&VERTEX_04, &FRAGMENT_04).unwrap();
+    match shaders.bindings.get("colormap") {
+        Err(Error::Binding { name, message }) => {
+            assert_eq!(name, "colormap");
+            assert!(message.contains("`Locals`"), "{}", message);
+        }
+        result => panic!("Expected a binding error, got {:?}", result),
+    }
+}
+
+#[test]
+fn matching_resources_can_be_bound() {
+    let shaders = binding_shaders();
+    let bindings = &shaders.bindings;
+
+    let locals = bindings.check_bind(0, "Locals", &[DescriptorType::UniformBuffer], &[]);
+    assert_eq!(locals.unwrap(), 0);
+    let colormap = bindings.check_bind(0, "colormap", &[DescriptorType::SampledImage], &[0]);
+    assert_eq!(colormap.unwrap(), 1);
+    let projection = bindings.check_bind(1, "Projection", &[DescriptorType::UniformBuffer], &[]);
+    assert_eq!(projection.unwrap(), 0);
+}
+
+#[test]
+fn wrong_descriptor_type_is_rejected() {
+    let shaders = binding_shaders();
+    let (name, message) = bind_error(shaders.bindings.check_bind(
+        0,
+        "colormap",
+        &[DescriptorType::Sampler],
+        &[],
+    ));
+    assert_eq!(name, "colormap");
+    assert!(message.contains("SampledImage"), "{}", message);
+}
+
+#[test]
+fn wrong_set_is_rejected() {
+    let shaders = binding_shaders();
+    let (name, message) = bind_error(shaders.bindings.check_bind(
+        0,
+        "Projection",
+        &[DescriptorType::UniformBuffer],
+        &[],
+    ));
+    assert_eq!(name, "Projection");
+    assert!(message.contains("declared in set 1"), "{}", message);
+}
+
+#[test]
+fn unknown_name_is_rejected() {
+    let shaders = binding_shaders();
+    let (name, message) = bind_error(shaders.bindings.check_bind(
+        0,
+        "normalmap",
+        &[DescriptorType::SampledImage],
+        &[],
+    ));
+    assert_eq!(name, "normalmap");
+    assert!(message.contains("no such descriptor"), "{}", message);
+}
+
+#[test]
+fn binding_twice_is_rejected() {
+    let shaders = binding_shaders();
+    let (name, message) = bind_error(shaders.bindings.check_bind(
+        0,
+        "colorsampler",
+        &[DescriptorType::Sampler],
+        &[0, 2],
+    ));
+    assert_eq!(name, "colorsampler");
+    assert!(message.contains("already bound"), "{}", message);
+}
@@ -0,0 +1,103 @@
+//! Checks the cameras look at the chapters' geometry the way the fixed
+//! camera shows it, and that their controls move them as documented.
+
+use glium_tutorial_but_its_rendy::{
+    camera::{FlyCamera, OrbitCamera},
+    math::Vec3,
+    Camera,
+};
+use rendy::hal::window::Extent2D;
+
+const EPSILON: f32 = 1e-5;
+
+const EXTENT: Extent2D = Extent2D {
+    width: 800,
+    height: 600,
+};
+
+/// Where `point` ends up in normalized device coordinates.
+fn project(camera: &Camera, point: Vec3) -> Vec3 {
+    let matrices = camera.projection(EXTENT);
+    let clip = matrices.projection * (matrices.view * point.extend(1.0));
+    clip.truncate() * (1.0 / clip.w)
+}
+
+fn assert_oriented_like_fixed(camera: &Camera) {
+    let fixed = Camera::Fixed;
+    for point in &[Vec3::new(0.5, 0.0, 0.0), Vec3::new(0.0, 0.5, 0.0)] {
+        let expected = project(&fixed, *point);
+        let actual = project(camera, *point);
+        assert!(
+            actual.x.signum() == expected.x.signum() || expected.x.abs() < EPSILON,
+            "{:?} is projected to {:?}, the fixed camera puts it at {:?}",
+            point,
+            actual,
+            expected
+        );
+        assert!(
+            actual.y.signum() == expected.y.signum() || expected.y.abs() < EPSILON,
+            "{:?} is projected to {:?}, the fixed camera puts it at {:?}",
+            point,
+            actual,
+            expected
+        );
+    }
+
+    let center = project(camera, Vec3::ZERO);
+    assert!(center.x.abs() < EPSILON && center.y.abs() < EPSILON);
+    assert!(center.z > 0.0 && center.z < 1.0, "{:?} is clipped", center);
+}
+
+#[test]
+fn cameras_start_out_like_the_fixed_camera() {
+    assert_oriented_like_fixed(&Camera::Orbit(OrbitCamera::default()));
+    assert_oriented_like_fixed(&Camera::Fly(FlyCamera::default()));
+}
+
+#[test]
+fn cameras_are_looked_up_by_name() {
+    match Camera::from_name("orbit") {
+        Some(Camera::Orbit(_)) => {}
+        camera => panic!("Expected an orbit camera, got {:?}", camera),
+    }
+    match Camera::from_name("fly") {
+        Some(Camera::Fly(_)) => {}
+        camera => panic!("Expected a fly camera, got {:?}", camera),
+    }
+    assert!(Camera::from_name("dolly").is_none());
+}
+
+#[test]
+fn orbit_keeps_its_distance() {
+    let mut orbit = OrbitCamera::default();
+    orbit.rotate(1.0, 0.5);
+    assert!(((orbit.eye() - orbit.target).length() - orbit.distance).abs() < EPSILON);
+
+    let distance = orbit.distance;
+    orbit.zoom(1.0);
+    assert!(orbit.distance < distance);
+    orbit.zoom(-2.0);
+    assert!(orbit.distance > distance);
+}
+
+#[test]
+fn orbit_pitch_is_clamped() {
+    let mut orbit = OrbitCamera::default();
+    orbit.rotate(0.0, 10.0);
+    assert!(orbit.pitch < std::f32::consts::FRAC_PI_2);
+    orbit.rotate(0.0, -20.0);
+    assert!(orbit.pitch > -std::f32::consts::FRAC_PI_2);
+}
+
+#[test]
+fn fly_moves_along_its_own_axes() {
+    let mut fly = FlyCamera::new(Vec3::ZERO);
+    fly.fly(Vec3::new(0.0, 0.0, 1.0));
+    assert!((fly.position - Vec3::Z).length() < EPSILON);
+
+    // Turning right a quarter turn makes +X forward.
+    let mut fly = FlyCamera::new(Vec3::ZERO);
+    fly.look(std::f32::consts::FRAC_PI_2, 0.0);
+    fly.fly(Vec3::new(0.0, 0.0, 1.0));
+    assert!((fly.position - Vec3::X).length() < EPSILON);
+}
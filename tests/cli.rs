@@ -0,0 +1,185 @@
+//! Checks the `tutorial` command line and the chapters it can run.
+
+use std::path::Path;
+
+use glium_tutorial_but_its_rendy::{
+    adapter::AdapterSelector,
+    chapters,
+    cli::{parse_size, Options},
+    config::PresentMode,
+    Camera, Redraw,
+};
+
+fn parse(args: &[&str]) -> Result<Options, String> {
+    Options::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn parses_a_windowed_run() {
+    let options = parse(&["--chapter", "06", "--size", "1280x720", "--camera", "orbit"]).unwrap();
+
+    assert_eq!(options.chapter.as_deref(), Some("06"));
+    assert_eq!(options.size, Some((1280, 720)));
+    match options.camera {
+        Some(Camera::Orbit(_)) => {}
+        other => panic!("expected an orbit camera, got {:?}", other),
+    }
+    assert!(!options.headless);
+    assert!(!options.hot_reload);
+}
+
+#[test]
+fn parses_a_headless_run() {
+    let options = parse(&[
+        "--headless",
+        "--chapter",
+        "03x1",
+        "--frames",
+        "10",
+        "--out",
+        "frame10.png",
+    ])
+    .unwrap();
+
+    assert!(options.headless);
+    assert_eq!(options.frames, 10);
+    assert_eq!(options.out.to_str(), Some("frame10.png"));
+    assert_eq!(options.size, None);
+}
+
+#[test]
+fn headless_run_keeps_the_camera() {
+    let options = parse(&["--headless", "--chapter", "06", "--camera", "fly"]).unwrap();
+    match options.camera {
+        Some(Camera::Fly(_)) => {}
+        other => panic!("expected a fly camera, got {:?}", other),
+    }
+}
+
+#[test]
+fn window_options_are_rejected_with_headless() {
+    for args in &[
+        &["--key-bindings", "keys.toml"][..],
+        &["--hot-reload"][..],
+        &["--redraw", "on-demand"][..],
+        &["--present-mode", "mailbox"][..],
+        &["--image-count", "2"][..],
+    ] {
+        let mut args = args.to_vec();
+        args.extend(&["--headless", "--chapter", "02"]);
+        let err = parse(&args).unwrap_err();
+        assert!(err.contains(args[0]), "{}", err);
+        assert!(err.contains("--headless"), "{}", err);
+    }
+}
+
+#[test]
+fn defaults_to_one_frame() {
+    let options = parse(&["--chapter", "01", "--headless"]).unwrap();
+    assert_eq!(options.frames, 1);
+    assert_eq!(options.out.to_str(), Some("frame.png"));
+}
+
+#[test]
+fn list_needs_no_chapter() {
+    assert!(parse(&["--list"]).unwrap().list);
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["--chapter"]).is_err());
+    assert!(parse(&["--chapter", "01", "--frames", "many"]).is_err());
+    assert!(parse(&["--chapter", "01", "--camera", "dolly"]).is_err());
+    assert!(parse(&["--chapter", "01", "--fullscreen"]).is_err());
+}
+
+#[test]
+fn parses_sizes() {
+    assert_eq!(parse_size("800x600"), Ok((800, 600)));
+    assert!(parse_size("800").is_err());
+    assert!(parse_size("800x600x2").is_err());
+    assert!(parse_size("0x600").is_err());
+    assert!(parse_size("widexhigh").is_err());
+}
+
+#[test]
+fn every_chapter_is_listed_once() {
+    let names: Vec<_> = chapters::CHAPTERS.iter().map(|entry| entry.name).collect();
+    assert_eq!(names, ["01", "02", "03", "03x1", "04", "05", "06"]);
+
+    for entry in chapters::CHAPTERS {
+        assert!(!entry.description.is_empty());
+        assert_eq!(
+            chapters::find(entry.name).map(|found| found.name),
+            Some(entry.name)
+        );
+    }
+    assert!(chapters::find("07").is_none());
+}
+
+#[test]
+fn collects_config_overrides() {
+    let options = parse(&[
+        "--chapter",
+        "02",
+        "--config",
+        "rendy.toml",
+        "--set",
+        "device.type=integrated",
+        "--set",
+        "heaps.linear_size=1048576",
+    ])
+    .unwrap();
+
+    assert_eq!(options.config.as_deref(), Some(Path::new("rendy.toml")));
+    assert_eq!(
+        options.overrides,
+        ["device.type=integrated", "heaps.linear_size=1048576"]
+    );
+}
+
+#[test]
+fn parses_adapter_selection() {
+    let options = parse(&["--chapter", "05", "--adapter", "geforce"]).unwrap();
+    assert_eq!(
+        options.adapter,
+        Some(AdapterSelector::Name("geforce".into()))
+    );
+
+    let options = parse(&["--adapter", "1", "--chapter", "05"]).unwrap();
+    assert_eq!(options.adapter, Some(AdapterSelector::Index(1)));
+
+    assert!(parse(&["--list-adapters"]).unwrap().list_adapters);
+}
+
+#[test]
+fn parses_present_options() {
+    let options = parse(&[
+        "--chapter",
+        "02",
+        "--present-mode",
+        "mailbox",
+        "--image-count",
+        "2",
+    ])
+    .unwrap();
+
+    assert_eq!(options.present_mode, Some(PresentMode::Mailbox));
+    assert_eq!(options.image_count, Some(2));
+
+    assert!(parse(&["--chapter", "02", "--present-mode", "vsync"]).is_err());
+    assert!(parse(&["--chapter", "02", "--image-count", "two"]).is_err());
+}
+
+#[test]
+fn parses_redraw_mode() {
+    let options = parse(&["--chapter", "04", "--redraw", "on-demand"]).unwrap();
+    assert_eq!(options.redraw, Some(Redraw::OnDemand));
+
+    let options = parse(&["--chapter", "02", "--redraw", "continuous"]).unwrap();
+    assert_eq!(options.redraw, Some(Redraw::Continuous));
+
+    assert_eq!(parse(&["--chapter", "02"]).unwrap().redraw, None);
+    assert!(parse(&["--chapter", "02", "--redraw", "sometimes"]).is_err());
+}
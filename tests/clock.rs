@@ -0,0 +1,63 @@
+//! Checks the clock's fixed step, pause and time scale.
+
+use std::time::Duration;
+
+use glium_tutorial_but_its_rendy::clock::{Clock, Step};
+
+#[test]
+fn fixed_step_ignores_wall_time() {
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    assert_eq!(clock.step(), Step::Fixed(Duration::from_millis(250)));
+    assert_eq!(clock.frame(), 0);
+
+    clock.tick();
+    std::thread::sleep(Duration::from_millis(20));
+    clock.tick();
+
+    assert_eq!(clock.frame(), 2);
+    assert_eq!(clock.delta(), 0.25);
+    assert_eq!(clock.elapsed(), 0.5);
+    assert_eq!(clock.wall(), 0.5);
+}
+
+#[test]
+fn pause_stops_elapsed_but_not_wall_time() {
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    clock.tick();
+    clock.pause();
+    assert!(clock.is_paused());
+    clock.tick();
+
+    assert_eq!(clock.delta(), 0.0);
+    assert_eq!(clock.wall_delta(), 0.25);
+    assert_eq!(clock.elapsed(), 0.25);
+    assert_eq!(clock.wall(), 0.5);
+
+    clock.resume();
+    clock.tick();
+    assert_eq!(clock.delta(), 0.25);
+    assert_eq!(clock.elapsed(), 0.5);
+}
+
+#[test]
+fn scale_applies_to_elapsed_time_only() {
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    clock.set_scale(2.0);
+    clock.tick();
+
+    assert_eq!(clock.scale(), 2.0);
+    assert_eq!(clock.delta(), 0.5);
+    assert_eq!(clock.elapsed(), 0.5);
+    assert_eq!(clock.wall_delta(), 0.25);
+}
+
+#[test]
+fn negative_scale_stops_time() {
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    clock.set_scale(-1.0);
+    clock.tick();
+
+    assert_eq!(clock.scale(), 0.0);
+    assert_eq!(clock.delta(), 0.0);
+    assert_eq!(clock.elapsed(), 0.0);
+}
@@ -0,0 +1,178 @@
+//! Checks the rendy configuration parses from TOML and command line overrides.
+
+use glium_tutorial_but_its_rendy::{
+    config::{DeviceType, PresentConfig, PresentMode, QueueConfig, DEFAULT_IMAGE_COUNT},
+    RendyConfig,
+};
+use rendy::hal::window;
+
+fn overrides(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn empty_config_is_the_default() {
+    assert_eq!(RendyConfig::parse("", &[]), Ok(RendyConfig::default()));
+    assert_eq!(
+        RendyConfig::default().queues,
+        QueueConfig {
+            family: None,
+            count: 1
+        }
+    );
+}
+
+#[test]
+fn parses_every_section() {
+    let config = RendyConfig::parse(
+        r#"
+        [device]
+        type = "integrated"
+
+        [queues]
+        family = 2
+        count = 4
+
+        [heaps]
+        max_heap_size = 536870912
+        linear_size = 16777216
+        max_chunk_size = 67108864
+        "#,
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(config.device.device_type, Some(DeviceType::Integrated));
+    assert_eq!(
+        config.queues,
+        QueueConfig {
+            family: Some(2),
+            count: 4
+        }
+    );
+    assert_eq!(config.heaps.max_heap_size, Some(512 << 20));
+    assert_eq!(config.heaps.linear_size, Some(16 << 20));
+    assert_eq!(config.heaps.max_chunk_size, Some(64 << 20));
+    assert_eq!(config.heaps.block_size_granularity, None);
+}
+
+#[test]
+fn overrides_replace_and_add_keys() {
+    let config = RendyConfig::parse(
+        "[heaps]\nlinear_size = 1024\nmax_chunk_size = 4096",
+        &overrides(&[
+            "heaps.linear_size=2048",
+            "device.type=cpu",
+            "queues.count = 2",
+        ]),
+    )
+    .unwrap();
+
+    assert_eq!(config.heaps.linear_size, Some(2048));
+    assert_eq!(config.heaps.max_chunk_size, Some(4096));
+    assert_eq!(config.device.device_type, Some(DeviceType::Cpu));
+    assert_eq!(config.queues.count, 2);
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+    assert!(RendyConfig::parse("[heaps]\narena_size = 1", &[]).is_err());
+    assert!(RendyConfig::parse("", &overrides(&["queue.count=1"])).is_err());
+}
+
+#[test]
+fn malformed_overrides_are_rejected() {
+    assert!(RendyConfig::parse("", &overrides(&["heaps.linear_size"])).is_err());
+    assert!(RendyConfig::parse("", &overrides(&["=1"])).is_err());
+    assert!(RendyConfig::parse("", &overrides(&["heaps.linear_size=big"])).is_err());
+    assert!(RendyConfig::parse("", &overrides(&["device.type=quantum"])).is_err());
+    assert!(RendyConfig::parse("[queues]\ncount = 1", &overrides(&["queues.count.x=1"])).is_err());
+}
+
+#[test]
+fn invalid_heap_sizes_are_rejected() {
+    let err = RendyConfig::parse("[heaps]\nlinear_size = 1000", &[]).unwrap_err();
+    assert!(err.contains("heaps.linear_size"), "{}", err);
+    let err = RendyConfig::parse("", &overrides(&["heaps.max_chunk_size=0"])).unwrap_err();
+    assert!(err.contains("heaps.max_chunk_size"), "{}", err);
+
+    let source = "[heaps]\nmax_chunk_size = 1024\nmin_device_allocation = 4096";
+    let err = RendyConfig::parse(source, &[]).unwrap_err();
+    assert!(err.contains("heaps.min_device_allocation"), "{}", err);
+
+    let source =
+        "[heaps]\nmax_heap_size = 3000\nmax_chunk_size = 4096\nmin_device_allocation = 4096";
+    assert!(RendyConfig::parse(source, &[]).is_ok());
+}
+
+#[test]
+fn requested_present_mode_is_used_when_supported() {
+    let present = PresentConfig {
+        mode: Some(PresentMode::Immediate),
+        image_count: None,
+    };
+    let supported =
+        window::PresentMode::FIFO | window::PresentMode::MAILBOX | window::PresentMode::IMMEDIATE;
+    assert_eq!(
+        present.choose_mode(supported),
+        window::PresentMode::IMMEDIATE
+    );
+    assert_eq!(
+        PresentConfig::default().choose_mode(supported),
+        window::PresentMode::FIFO
+    );
+}
+
+#[test]
+fn present_mode_falls_back_to_a_similar_one() {
+    let present = |mode| PresentConfig {
+        mode: Some(mode),
+        image_count: None,
+    };
+    let supported = window::PresentMode::FIFO | window::PresentMode::MAILBOX;
+
+    assert_eq!(
+        present(PresentMode::Immediate).choose_mode(supported),
+        window::PresentMode::MAILBOX
+    );
+    assert_eq!(
+        present(PresentMode::Relaxed).choose_mode(supported),
+        window::PresentMode::FIFO
+    );
+    assert_eq!(
+        present(PresentMode::Mailbox).choose_mode(window::PresentMode::FIFO),
+        window::PresentMode::FIFO
+    );
+}
+
+#[test]
+fn image_count_is_kept_within_the_surface_limits() {
+    let present = |image_count| PresentConfig {
+        mode: None,
+        image_count,
+    };
+
+    assert_eq!(present(None).choose_image_count(2..=8), DEFAULT_IMAGE_COUNT);
+    assert_eq!(present(None).choose_image_count(1..=2), 2);
+    assert_eq!(present(Some(2)).choose_image_count(2..=8), 2);
+    assert_eq!(present(Some(1)).choose_image_count(2..=8), 2);
+    assert_eq!(present(Some(16)).choose_image_count(2..=8), 8);
+}
+
+#[test]
+fn present_section_parses() {
+    let config = RendyConfig::parse(
+        "[present]\nmode = \"mailbox\"\nimage_count = 2",
+        &overrides(&["present.mode=relaxed"]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.present,
+        PresentConfig {
+            mode: Some(PresentMode::Relaxed),
+            image_count: Some(2),
+        }
+    );
+    assert!(RendyConfig::parse("[present]\nmode = \"vsync\"", &[]).is_err());
+}
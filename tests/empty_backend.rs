@@ -0,0 +1,75 @@
+//! Puts every chapter into a render graph for rendy's `empty` backend, which
+//! needs no GPU or driver at all.
+//!
+//! The empty backend has no adapters, so nothing here creates a factory. The
+//! graph is only assembled, which is as far as chapters can get without one.
+//!
+//! Only runs with the `empty` feature, e.g.
+//! `cargo test --no-default-features --features empty`.
+
+#![cfg(feature = "empty")]
+
+use glium_tutorial_but_its_rendy::{
+    app::CLEAR_COLOR, chapters::*, headless::FORMAT, shader::Shaders, Chapter, FrameContext,
+};
+use rendy::{empty::Backend, graph::GraphBuilder, hal};
+
+fn assemble<C: Chapter>(chapter: &C) {
+    let mut graph_builder = GraphBuilder::<Backend, FrameContext<C::Aux>>::new();
+
+    let color = graph_builder.create_image(
+        hal::image::Kind::D2(64, 64, 1, 1),
+        1,
+        FORMAT,
+        Some(CLEAR_COLOR),
+    );
+
+    let subpass = match chapter.subpass::<Backend>() {
+        Ok(subpass) => subpass,
+        Err(err) => panic!("{:?}", err),
+    };
+    graph_builder.add_node(subpass.with_color(color).into_pass());
+}
+
+/// Checks what the chapter's pipeline is created from: some descriptor sets
+/// or push constants, and one vertex buffer with attributes.
+fn check_pipeline_inputs<C>(chapter: &C)
+where
+    C: Chapter<Compiled = Shaders>,
+{
+    let shaders = chapter.compile().unwrap();
+
+    let layout = shaders.layout();
+    assert!(
+        !layout.sets.is_empty() || !layout.push_constants.is_empty(),
+        "{:?}",
+        layout
+    );
+
+    let vertices = shaders.vertices();
+    assert_eq!(vertices.len(), 1);
+    let (elements, stride, _) = &vertices[0];
+    assert!(!elements.is_empty());
+    assert!(*stride > 0);
+}
+
+#[test]
+fn chapters_assemble_without_a_gpu() {
+    assemble(&Chapter01);
+    assemble(&Chapter02);
+    assemble(&Chapter03);
+    assemble(&Chapter03x1);
+    assemble(&Chapter04);
+    assemble(&Chapter05);
+    assemble(&Chapter06::new().unwrap());
+}
+
+#[test]
+fn chapters_reflect_their_pipeline_inputs() {
+    check_pipeline_inputs(&Chapter02);
+    check_pipeline_inputs(&Chapter03);
+    check_pipeline_inputs(&Chapter03x1);
+    check_pipeline_inputs(&Chapter04);
+    check_pipeline_inputs(&Chapter05);
+    check_pipeline_inputs(&Chapter06::new().unwrap());
+}
@@ -0,0 +1,65 @@
+//! Checks the frame context follows the clock it is ticked with.
+
+use std::time::Duration;
+
+use glium_tutorial_but_its_rendy::{Clock, FrameContext, Input};
+use rendy::hal::window::Extent2D;
+
+#[test]
+fn tick_follows_the_clock() {
+    let extent = Extent2D {
+        width: 320,
+        height: 240,
+    };
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    let mut ctx = FrameContext::new(7u32, Input::default());
+    assert_eq!(ctx.frame, 0);
+
+    clock.tick();
+    clock.tick();
+    ctx.tick(&clock, extent);
+
+    assert_eq!(ctx.frame, 2);
+    assert_eq!(ctx.time, 0.5);
+    assert_eq!(ctx.delta, 0.25);
+    assert_eq!((ctx.extent.width, ctx.extent.height), (320, 240));
+    assert_eq!(ctx.user, 7);
+}
+
+#[test]
+fn paused_clock_stops_time() {
+    let mut clock = Clock::fixed(Duration::from_millis(250));
+    let mut ctx = FrameContext::new((), Input::default());
+
+    clock.tick();
+    clock.pause();
+    clock.tick();
+    ctx.tick(
+        &clock,
+        Extent2D {
+            width: 1,
+            height: 1,
+        },
+    );
+
+    assert_eq!(ctx.frame, 2);
+    assert_eq!(ctx.time, 0.25);
+    assert_eq!(ctx.delta, 0.0);
+}
+
+#[test]
+fn skipped_gap_is_not_counted() {
+    let mut clock = Clock::new();
+    clock.tick();
+    std::thread::sleep(Duration::from_millis(20));
+    clock.skip_gap();
+    clock.tick();
+
+    assert_eq!(clock.frame(), 2);
+    assert_eq!(clock.delta(), 0.0);
+    assert_eq!(clock.elapsed(), 0.0);
+
+    std::thread::sleep(Duration::from_millis(20));
+    clock.tick();
+    assert!(clock.delta() >= 0.02);
+}
@@ -0,0 +1,151 @@
+//! Renders every chapter offscreen and compares the result against the
+//! reference images in `tests/golden`.
+//!
+//! Run with `UPDATE_GOLDEN=1` to (re)write the reference images instead of
+//! comparing against them. A missing reference fails the test, and the
+//! rendered image is written to `target/golden` for review, like the actual
+//! image and diff of a mismatch.
+//!
+//! Needs a Vulkan driver, so these only run with the `vulkan` feature.
+
+#![cfg(feature = "vulkan")]
+
+use std::path::{Path, PathBuf};
+
+use glium_tutorial_but_its_rendy::{chapters::*, Chapter, Headless};
+use image::{Rgba, RgbaImage};
+
+const WIDTH: u32 = 256;
+const HEIGHT: u32 = 256;
+const FRAMES: usize = 3;
+
+/// Largest per-channel difference still considered a match.
+const TOLERANCE: u8 = 2;
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn failure_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("target/golden")
+}
+
+fn check<C>(name: &str, chapter: C, aux: C::Aux)
+where
+    C: Chapter,
+{
+    let actual = Headless::new(WIDTH, HEIGHT)
+        .with_frames(FRAMES)
+        .render_at(chapter, aux)
+        .unwrap();
+
+    let reference_path = golden_dir().join(format!("{}.png", name));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        actual.save(&reference_path).unwrap();
+        return;
+    }
+
+    let reference = match image::open(&reference_path) {
+        Ok(reference) => reference.to_rgba(),
+        Err(err) => {
+            let dir = failure_dir();
+            std::fs::create_dir_all(&dir).unwrap();
+            let actual_path = dir.join(format!("{}.actual.png", name));
+            actual.save(&actual_path).unwrap();
+            panic!(
+                "No reference image {} for chapter {} ({}), wrote {}. Check it and copy it there.",
+                reference_path.display(),
+                name,
+                err,
+                actual_path.display(),
+            );
+        }
+    };
+
+    assert_eq!(
+        reference.dimensions(),
+        actual.dimensions(),
+        "Reference image for chapter {} has the wrong size",
+        name,
+    );
+
+    let (diff, mismatched) = diff(&reference, &actual);
+    if mismatched > 0 {
+        let dir = failure_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let actual_path = dir.join(format!("{}.actual.png", name));
+        let diff_path = dir.join(format!("{}.diff.png", name));
+        actual.save(&actual_path).unwrap();
+        diff.save(&diff_path).unwrap();
+
+        panic!(
+            "Chapter {} differs from the reference in {} pixels, see {} and {}",
+            name,
+            mismatched,
+            actual_path.display(),
+            diff_path.display(),
+        );
+    }
+}
+
+/// Returns an image with mismatched pixels in red on a dimmed copy of the
+/// reference, and the number of mismatched pixels.
+fn diff(reference: &RgbaImage, actual: &RgbaImage) -> (RgbaImage, usize) {
+    let mut mismatched = 0;
+    let diff = RgbaImage::from_fn(reference.width(), reference.height(), |x, y| {
+        let expected = reference.get_pixel(x, y);
+        let got = actual.get_pixel(x, y);
+
+        let matches = expected
+            .0
+            .iter()
+            .zip(got.0.iter())
+            .all(|(&a, &b)| (a as i16 - b as i16).abs() <= TOLERANCE as i16);
+
+        if matches {
+            let [r, g, b, _] = expected.0;
+            Rgba([r / 4, g / 4, b / 4, 255])
+        } else {
+            mismatched += 1;
+            Rgba([255, 0, 0, 255])
+        }
+    });
+
+    (diff, mismatched)
+}
+
+#[test]
+fn chapter_01() {
+    check("01", Chapter01, ());
+}
+
+#[test]
+fn chapter_02() {
+    check("02", Chapter02, ());
+}
+
+#[test]
+fn chapter_03() {
+    check("03", Chapter03, 0.25);
+}
+
+#[test]
+fn chapter_03x1() {
+    check("03x1", Chapter03x1, 0.25);
+}
+
+#[test]
+fn chapter_04() {
+    check("04", Chapter04, 0.25);
+}
+
+#[test]
+fn chapter_05() {
+    check("05", Chapter05, 0.25);
+}
+
+#[test]
+fn chapter_06() {
+    check("06", Chapter06::new().unwrap(), 0.5);
+}
@@ -0,0 +1,171 @@
+//! Checks the input state tracks buttons across frames and that key bindings
+//! parse from TOML.
+
+use glium_tutorial_but_its_rendy::{
+    camera::FlyCamera,
+    input::{action, Button, KeyBindings},
+    math::Vec3,
+    Camera, Input,
+};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const W: Button = Button::Key(VirtualKeyCode::W);
+
+#[test]
+fn presses_last_a_frame() {
+    let mut input = Input::default();
+    input.press(W);
+    assert!(input.is_down(W));
+    assert!(input.just_pressed(W));
+
+    input.end_frame();
+    // Held keys repeat their presses.
+    input.press(W);
+    assert!(input.is_down(W));
+    assert!(!input.just_pressed(W));
+
+    input.release(W);
+    assert!(!input.is_down(W));
+    assert!(input.just_released(W));
+
+    input.end_frame();
+    assert!(!input.just_released(W));
+}
+
+#[test]
+fn any_held_button_counts() {
+    let mut input = Input::default();
+    assert!(!input.is_any_down());
+
+    input.press(Button::Mouse(MouseButton::Left));
+    input.press(W);
+    input.release(W);
+    assert!(input.is_any_down());
+
+    input.release(Button::Mouse(MouseButton::Left));
+    assert!(!input.is_any_down());
+}
+
+#[test]
+fn cursor_movement_accumulates() {
+    let mut input = Input::default();
+    input.move_cursor(10.0, 10.0);
+    assert_eq!(input.cursor_delta(), (0.0, 0.0));
+
+    input.move_cursor(12.0, 9.0);
+    input.move_cursor(15.0, 9.0);
+    input.add_scroll(1.0);
+    input.add_scroll(0.5);
+    assert_eq!(input.cursor(), Some((15.0, 9.0)));
+    assert_eq!(input.cursor_delta(), (5.0, -1.0));
+    assert_eq!(input.scroll(), 1.5);
+
+    input.end_frame();
+    assert_eq!(input.cursor_delta(), (0.0, 0.0));
+    assert_eq!(input.scroll(), 0.0);
+}
+
+#[test]
+fn actions_follow_their_bindings() {
+    let mut input = Input::default();
+    input.press(W);
+    assert!(input.is_action_down(action::FORWARD));
+    assert!(input.action_just_pressed(action::FORWARD));
+    assert!(!input.is_action_down(action::BACK));
+    assert!(!input.is_action_down("jump"));
+
+    let mut bindings = KeyBindings::empty();
+    bindings.bind(action::BACK, W);
+    input.set_bindings(bindings);
+    assert!(!input.is_action_down(action::FORWARD));
+    assert!(input.is_action_down(action::BACK));
+}
+
+#[test]
+fn bindings_parse_from_toml() {
+    let bindings = KeyBindings::parse(
+        r#"
+        forward = ["Up", "W"]
+        look = ["MouseRight", "Mouse4"]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        bindings.buttons(action::FORWARD),
+        &[Button::Key(VirtualKeyCode::Up), W][..]
+    );
+    assert_eq!(
+        bindings.buttons(action::LOOK),
+        &[
+            Button::Mouse(MouseButton::Right),
+            Button::Mouse(MouseButton::Other(4))
+        ][..]
+    );
+    assert!(bindings.buttons(action::BACK).is_empty());
+}
+
+#[test]
+fn unknown_buttons_are_reported() {
+    let err = KeyBindings::parse(r#"forward = ["Warp"]"#).unwrap_err();
+    assert!(
+        err.contains("`Warp`") && err.contains("`forward`"),
+        "{}",
+        err
+    );
+
+    let err = KeyBindings::parse(r#"look = ["MouseSide"]"#).unwrap_err();
+    assert!(err.contains("`MouseSide`"), "{}", err);
+}
+
+#[test]
+fn loaded_bindings_keep_the_other_defaults() {
+    let path = std::env::temp_dir().join("glium_tutorial_but_its_rendy_bindings.toml");
+    std::fs::write(&path, "forward = [\"Up\"]\n").unwrap();
+    let bindings = KeyBindings::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let defaults = KeyBindings::default();
+    assert_eq!(
+        bindings.buttons(action::FORWARD),
+        &[Button::Key(VirtualKeyCode::Up)][..]
+    );
+    assert_eq!(
+        bindings.buttons(action::BACK),
+        defaults.buttons(action::BACK)
+    );
+}
+
+#[test]
+fn buttons_print_as_they_parse() {
+    for button in &[
+        W,
+        Button::Mouse(MouseButton::Left),
+        Button::Mouse(MouseButton::Other(7)),
+    ] {
+        assert_eq!(button.to_string().parse::<Button>(), Ok(*button));
+    }
+}
+
+#[test]
+fn fly_camera_follows_actions() {
+    let mut camera = Camera::Fly(FlyCamera::new(Vec3::ZERO));
+    let mut input = Input::default();
+
+    input.press(W);
+    camera.update(&input, 0.5);
+    match &camera {
+        Camera::Fly(fly) => assert!((fly.position - Vec3::Z * 0.5).length() < 1e-5),
+        _ => unreachable!(),
+    }
+
+    assert!(!camera.grabs_cursor());
+    input.press(Button::Mouse(MouseButton::Left));
+    camera.update(&input, 0.0);
+    assert!(camera.grabs_cursor());
+
+    input.end_frame();
+    input.press(Button::Key(VirtualKeyCode::Escape));
+    camera.update(&input, 0.0);
+    assert!(!camera.grabs_cursor());
+}
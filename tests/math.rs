@@ -0,0 +1,136 @@
+//! Checks the math module against the Vulkan clip space conventions.
+
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use glium_tutorial_but_its_rendy::math::{Mat4, Quat, Vec3, Vec4};
+
+const EPSILON: f32 = 1e-5;
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!(
+        (a - b).length() < EPSILON,
+        "{:?} is not close to {:?}",
+        a,
+        b
+    );
+}
+
+fn assert_mat_close(a: Mat4, b: Mat4) {
+    let close = a
+        .cols
+        .iter()
+        .flatten()
+        .zip(b.cols.iter().flatten())
+        .all(|(a, b)| (a - b).abs() < EPSILON);
+    assert!(close, "{:?} is not close to {:?}", a, b);
+}
+
+#[test]
+fn vector_products() {
+    assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+    assert_eq!(Vec3::new(1.0, 2.0, 3.0).dot(Vec3::new(4.0, 5.0, 6.0)), 32.0);
+    assert_close(
+        Vec3::new(3.0, 0.0, 4.0).normalize(),
+        Vec3::new(0.6, 0.0, 0.8),
+    );
+}
+
+#[test]
+fn translate_rotate_scale() {
+    let m = Mat4::IDENTITY
+        .translate(Vec3::new(1.0, 2.0, 3.0))
+        .rotate(Quat::from_axis_angle(Vec3::Z, FRAC_PI_2))
+        .scale(Vec3::new(2.0, 2.0, 2.0));
+
+    // Scaled, then rotated, then translated.
+    assert_close(m.transform_point(Vec3::X), Vec3::new(1.0, 4.0, 3.0));
+    assert_close(m.transform_vector(Vec3::X), Vec3::new(0.0, 2.0, 0.0));
+}
+
+#[test]
+fn quaternion_rotation_matches_matrix() {
+    let q = Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 1.2);
+    let v = Vec3::new(0.3, -2.0, 5.0);
+    assert_close(q * v, Mat4::from_quat(q).transform_point(v));
+
+    let z = Quat::from_axis_angle(Vec3::Z, 0.7);
+    assert_mat_close(Mat4::from_quat(z), Mat4::from_rotation_z(0.7));
+}
+
+#[test]
+fn quaternion_composition() {
+    let a = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+    let b = Quat::from_axis_angle(Vec3::X, FRAC_PI_2);
+    // `a * b` applies `b` first: Y goes to Z, which stays put around Z.
+    assert_close((a * b) * Vec3::Y, Vec3::Z);
+    assert_close((a * a.conjugate()) * Vec3::Y, Vec3::Y);
+}
+
+#[test]
+fn matrix_product_and_transpose() {
+    let a = Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0));
+    let b = Mat4::from_scale(Vec3::new(2.0, 3.0, 4.0));
+    assert_close((a * b).transform_point(Vec3::ONE), Vec3::new(3.0, 3.0, 4.0));
+    assert_eq!(a.transpose().transpose(), a);
+    assert_eq!(a.transpose().cols[0][3], 1.0);
+}
+
+#[test]
+fn perspective_maps_near_and_far_to_zero_and_one() {
+    let projection = Mat4::perspective(FRAC_PI_2, 2.0, 0.1, 100.0);
+
+    assert_close(
+        projection.transform_point(Vec3::new(0.0, 0.0, -0.1)),
+        Vec3::new(0.0, 0.0, 0.0),
+    );
+    assert_close(
+        projection.transform_point(Vec3::new(0.0, 0.0, -100.0)),
+        Vec3::new(0.0, 0.0, 1.0),
+    );
+
+    // Up in view space is up on screen, which is -Y in Vulkan clip space.
+    let top = projection.transform_point(Vec3::new(0.0, 1.0, -1.0));
+    assert!((top.y + 1.0).abs() < EPSILON, "{:?}", top);
+
+    // The horizontal extent is divided by the aspect ratio.
+    let right = projection.transform_point(Vec3::new(2.0, 0.0, -1.0));
+    assert!((right.x - 1.0).abs() < EPSILON, "{:?}", right);
+}
+
+#[test]
+fn orthographic_maps_box_to_clip_space() {
+    let projection = Mat4::orthographic(-2.0, 2.0, -1.0, 1.0, 0.0, 10.0);
+
+    assert_close(
+        projection.transform_point(Vec3::new(-2.0, 1.0, 0.0)),
+        Vec3::new(-1.0, -1.0, 0.0),
+    );
+    assert_close(
+        projection.transform_point(Vec3::new(2.0, -1.0, -10.0)),
+        Vec3::new(1.0, 1.0, 1.0),
+    );
+}
+
+#[test]
+fn look_at_moves_target_onto_negative_z() {
+    let eye = Vec3::new(3.0, 4.0, 5.0);
+    let view = Mat4::look_at(eye, Vec3::ZERO, Vec3::Y);
+
+    assert_close(view.transform_point(eye), Vec3::ZERO);
+    assert_close(
+        view.transform_point(Vec3::ZERO),
+        Vec3::new(0.0, 0.0, -eye.length()),
+    );
+
+    let up = view.transform_vector(Vec3::Y);
+    assert!(up.y > 0.0 && up.x.abs() < EPSILON, "{:?}", up);
+}
+
+#[test]
+fn rotation_z_turns_counter_clockwise() {
+    assert_close(Mat4::from_rotation_z(PI).transform_point(Vec3::X), -Vec3::X);
+    assert_eq!(
+        Mat4::IDENTITY * Vec4::new(1.0, 2.0, 3.0, 4.0),
+        Vec4::new(1.0, 2.0, 3.0, 4.0)
+    );
+}
@@ -0,0 +1,69 @@
+//! Checks the projection keeps geometry proportional, and that every chapter
+//! declares the projection block it is written to.
+
+use glium_tutorial_but_its_rendy::{
+    chapters::shaders::*,
+    math::Vec3,
+    projection::Projection,
+    shader::{ShaderSource, Shaders},
+};
+use rendy::hal::window::Extent2D;
+
+fn project(extent: Extent2D, point: Vec3) -> Vec3 {
+    let matrices = Projection::for_extent(extent);
+    (matrices.projection * (matrices.view * point.extend(1.0))).truncate()
+}
+
+#[test]
+fn square_framebuffer_is_unchanged() {
+    let extent = Extent2D {
+        width: 256,
+        height: 256,
+    };
+    assert_eq!(
+        project(extent, Vec3::new(0.5, -0.5, 0.0)),
+        Vec3::new(0.5, -0.5, 0.0)
+    );
+}
+
+#[test]
+fn longer_axis_is_extended() {
+    let wide = Extent2D {
+        width: 800,
+        height: 400,
+    };
+    assert_eq!(
+        project(wide, Vec3::new(1.0, 1.0, 0.0)),
+        Vec3::new(0.5, 1.0, 0.0)
+    );
+
+    let tall = Extent2D {
+        width: 400,
+        height: 800,
+    };
+    assert_eq!(
+        project(tall, Vec3::new(1.0, 1.0, 0.0)),
+        Vec3::new(1.0, 0.5, 0.0)
+    );
+}
+
+#[test]
+fn chapters_declare_the_projection() {
+    let chapters: &[(&ShaderSource, &ShaderSource)] = &[
+        (&VERTEX_02, &FRAGMENT_02),
+        (&VERTEX_03, &FRAGMENT_03),
+        (&VERTEX_03X1, &FRAGMENT_03X1),
+        (&VERTEX_04, &FRAGMENT_04),
+        (&VERTEX_05, &FRAGMENT_05),
+        (&VERTEX_06, &FRAGMENT_06),
+    ];
+
+    for (vertex, fragment) in chapters {
+        let shaders = Shaders::compile(vertex, fragment).unwrap();
+        shaders.check_uniform::<Projection>("Projection").unwrap();
+        assert_eq!(
+            shaders.bindings.get("Projection").unwrap().set,
+            Projection::SET
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Checks push constant types against the blocks reflected from their
+//! shaders. None of this needs a GPU.
+
+use glium_tutorial_but_its_rendy::{
+    chapters::shaders::*, shader::Shaders, Error, PushConstants, Uniform,
+};
+use rendy::hal::pso::ShaderStageFlags;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+#[uniform(std430)]
+struct Time {
+    t: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+#[uniform(std430)]
+struct TooLarge {
+    a: [[f32; 4]; 4],
+    b: [[f32; 4]; 4],
+    c: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+#[uniform(std430)]
+struct Offset {
+    offset: [f32; 2],
+}
+
+#[test]
+fn reflected_range_is_in_the_layout() {
+    let shaders = Shaders::compile(&VERTEX_03X1, &FRAGMENT_03X1).unwrap();
+    assert_eq!(
+        shaders.layout().push_constants,
+        vec![(ShaderStageFlags::VERTEX, 0..4)]
+    );
+
+    let push_constants = shaders.push_constants::<Time>("Locals").unwrap();
+    assert_eq!(push_constants.stages(), ShaderStageFlags::VERTEX);
+    assert_eq!(push_constants.range(), 0..4);
+}
+
+#[test]
+fn values_are_serialized_to_words() {
+    assert_eq!(
+        PushConstants::<Time>::words(&Time { t: 0.5 }),
+        vec![0.5f32.to_bits()]
+    );
+    assert_eq!(
+        PushConstants::<Offset>::words(&Offset {
+            offset: [1.0, -2.0]
+        }),
+        vec![1.0f32.to_bits(), (-2.0f32).to_bits()]
+    );
+}
+
+#[test]
+fn size_limit_is_enforced() {
+    match PushConstants::<TooLarge>::new(ShaderStageFlags::VERTEX) {
+        Err(Error::PushConstants(message)) => assert!(message.contains("132 bytes"), "{}", message),
+        result => panic!("Expected a push constant error, got {:?}", result),
+    }
+}
+
+#[test]
+fn mismatched_block_is_reported() {
+    let shaders = Shaders::compile(&VERTEX_03X1, &FRAGMENT_03X1).unwrap();
+    match shaders.push_constants::<Offset>("Locals") {
+        Err(Error::UniformLayout { .. }) => {}
+        result => panic!("Expected a uniform layout error, got {:?}", result),
+    }
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(location = 0) in vec2 a_Pos;
+layout(location = 0) out vec2 tex_coords;
+
+layout(set = 0, binding = 0) uniform Locals {
+    float t;
+};
+
+layout(set = 1, binding = 0) uniform Projection {
+    mat4 projection;
+};
+
+void main() {
+    tex_coords = a_Pos;
+    gl_Position = projection * vec4(a_Pos.x + t, a_Pos.y, 0.0, 1.0);
+}
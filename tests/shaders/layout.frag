@@ -0,0 +1,8 @@
+#version 450
+
+layout(location = 0) in vec3 color_out;
+layout(location = 0) out vec4 color;
+
+void main() {
+    color = vec4(color_out, 1.0);
+}
@@ -1,7 +1,10 @@
 #version 450
 
 layout(location = 0) in vec2 a_Pos;
+layout(location = 1) in vec3 color;
+layout(location = 0) out vec3 color_out;
 
 void main() {
+    color_out = color;
     gl_Position = vec4(a_Pos, 0.0, 1.0);
 }
@@ -0,0 +1,16 @@
+#version 450
+
+layout(location = 0) in vec2 a_Pos;
+layout(location = 0) out vec3 color_out;
+
+layout(set = 0, binding = 0) uniform Lights {
+    vec3 direction;
+    float intensity;
+    float weights[3];
+    vec3 colors[2];
+};
+
+void main() {
+    color_out = intensity * (weights[0] * colors[0] + weights[2] * colors[1]) + direction;
+    gl_Position = vec4(a_Pos, 0.0, 1.0);
+}
@@ -0,0 +1,164 @@
+//! Checks Rust uniform types against the blocks reflected from their shaders.
+//! None of this needs a GPU.
+
+use glium_tutorial_but_its_rendy::{
+    chapters::shaders::*,
+    shader::Shaders,
+    uniform::{BlockLayout, GlslArray, GlslType},
+    Error, Uniform,
+};
+
+mod test_shaders {
+    // Every test shader is included, but only some are used here.
+    #![allow(dead_code)]
+
+    use glium_tutorial_but_its_rendy::shader::ShaderSource;
+    use rendy::hal::pso::ShaderStageFlags;
+
+    include!(concat!(env!("OUT_DIR"), "/test_shaders.rs"));
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct Matrix {
+    matrix: [[f32; 4]; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct Time {
+    t: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct Misaligned {
+    t: f32,
+    offset: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct TooSmall {
+    matrix: [f32; 4],
+}
+
+/// `float weights[3]` and `vec3 colors[2]` both have a 16 byte stride.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct Lights {
+    direction: [f32; 3],
+    intensity: f32,
+    weights: GlslArray<[[f32; 4]; 3]>,
+    colors: GlslArray<[[f32; 4]; 2]>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct PackedWeights {
+    direction: [f32; 3],
+    intensity: f32,
+    weights: GlslArray<[f32; 3]>,
+    colors: GlslArray<[[f32; 4]; 2]>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct PackedColors {
+    direction: [f32; 3],
+    intensity: f32,
+    weights: GlslArray<[[f32; 4]; 3]>,
+    colors: GlslArray<[[f32; 3]; 2]>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Uniform)]
+struct Offset {
+    offset: [f32; 2],
+}
+
+fn lights() -> Shaders {
+    Shaders::compile(&test_shaders::VERTEX_STD140, &test_shaders::FRAGMENT_LAYOUT).unwrap()
+}
+
+fn details(result: Result<(), Error>) -> String {
+    match result {
+        Err(Error::UniformLayout { details, .. }) => details,
+        result => panic!("Expected a uniform layout error, got {:?}", result),
+    }
+}
+
+#[test]
+fn matching_uniforms_pass() {
+    let shaders = Shaders::compile(&VERTEX_03, &FRAGMENT_03).unwrap();
+    shaders.check_uniform::<Time>("Locals").unwrap();
+
+    let shaders = Shaders::compile(&VERTEX_04, &FRAGMENT_04).unwrap();
+    shaders.check_uniform::<Matrix>("Locals").unwrap();
+}
+
+#[test]
+fn std140_violation_is_reported() {
+    let shaders = Shaders::compile(&VERTEX_03, &FRAGMENT_03).unwrap();
+    let details = details(shaders.check_uniform::<Misaligned>("Locals"));
+    assert!(
+        details.contains("`offset` is at offset 4, Std140 places it at 16"),
+        "{}",
+        details
+    );
+    assert!(details.contains("declares 2 members"), "{}", details);
+}
+
+#[test]
+fn wrong_size_is_reported() {
+    let shaders = Shaders::compile(&VERTEX_04, &FRAGMENT_04).unwrap();
+    let details = details(shaders.check_uniform::<TooSmall>("Locals"));
+    assert!(details.contains("`matrix` is 16 bytes"), "{}", details);
+}
+
+#[test]
+fn unknown_block_is_reported() {
+    let shaders = Shaders::compile(&VERTEX_04, &FRAGMENT_04).unwrap();
+    match shaders.check_uniform::<Matrix>("Globals") {
+        Err(Error::Binding { name, .. }) => assert_eq!(name, "Globals"),
+        result => panic!("Expected a binding error, got {:?}", result),
+    }
+}
+
+#[test]
+fn std140_arrays_pass() {
+    lights().check_uniform::<Lights>("Lights").unwrap();
+}
+
+#[test]
+fn std140_array_stride_is_reported() {
+    let details = details(lights().check_uniform::<PackedWeights>("Lights"));
+    assert!(
+        details.contains("`colors` is at offset 28, Std140 places it at 64"),
+        "{}",
+        details
+    );
+}
+
+#[test]
+fn struct_size_is_checked_against_the_block() {
+    let details = details(lights().check_uniform::<PackedColors>("Lights"));
+    assert!(
+        details.contains("is 88 bytes, Std140 needs 96 for its members"),
+        "{}",
+        details
+    );
+    assert!(
+        details.contains("is 88 bytes, block `Lights` is 96"),
+        "{}",
+        details
+    );
+}
+
+#[test]
+fn nested_structs_round_up_under_std140() {
+    assert_eq!(Offset::align(BlockLayout::Std140), 16);
+    assert_eq!(Offset::size(BlockLayout::Std140), 16);
+    assert_eq!(Offset::align(BlockLayout::Std430), 8);
+    assert_eq!(Offset::size(BlockLayout::Std430), 8);
+}
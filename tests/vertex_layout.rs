@@ -0,0 +1,157 @@
+//! Checks Rust vertex types against the inputs reflected from their vertex
+//! shaders. None of this needs a GPU.
+
+use glium_tutorial_but_its_rendy::{
+    chapters::*,
+    shader::Shaders,
+    vertex::{self, AttributeDesc},
+    Chapter, Error, Vertex,
+};
+use rendy::{core::types::vertex::AsVertex, hal::format::Format};
+
+// Building a subpass needs no device, so any enabled backend will do.
+#[cfg(all(feature = "empty", not(feature = "vulkan")))]
+use rendy::empty::Backend;
+#[cfg(feature = "vulkan")]
+use rendy::vulkan::Backend;
+
+mod shaders {
+    // Every test shader is included, but only some are used here.
+    #![allow(dead_code)]
+
+    use glium_tutorial_but_its_rendy::shader::ShaderSource;
+    use rendy::hal::pso::ShaderStageFlags;
+
+    include!(concat!(env!("OUT_DIR"), "/test_shaders.rs"));
+}
+
+use shaders::{FRAGMENT_LAYOUT as FRAGMENT, VERTEX_LAYOUT as VERTEX};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct Matching {
+    #[vertex(name = "a_Pos")]
+    position: [f32; 2],
+    color: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct WrongFormat {
+    #[vertex(name = "a_Pos", format = "Rgb32Sfloat")]
+    position: [f32; 2],
+    color: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct WrongName {
+    position: [f32; 2],
+    color: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct MissingAttribute {
+    #[vertex(name = "a_Pos")]
+    position: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct Padded {
+    #[vertex(name = "a_Pos", format = "R8Unorm")]
+    flag: u8,
+    color: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Vertex)]
+struct Overridden {
+    #[vertex(name = "a_Pos")]
+    position: [f32; 2],
+    #[vertex(name = "tint", format = "Rgba8Unorm")]
+    color: [u8; 4],
+}
+
+fn details(result: Result<(), Error>) -> String {
+    match result {
+        Err(Error::VertexLayout { details, .. }) => details,
+        result => panic!("Expected a vertex layout error, got {:?}", result),
+    }
+}
+
+#[test]
+fn matching_vertex_passes() {
+    let shaders = Shaders::compile(&VERTEX, &FRAGMENT).unwrap();
+    shaders.check_vertex::<Matching>().unwrap();
+}
+
+#[test]
+fn wrong_format_is_reported() {
+    let shaders = Shaders::compile(&VERTEX, &FRAGMENT).unwrap();
+    let details = details(shaders.check_vertex::<WrongFormat>());
+    assert!(details.contains("Rgb32Sfloat"), "{}", details);
+    assert!(details.contains("offset"), "{}", details);
+    assert!(details.contains("stride"), "{}", details);
+}
+
+#[test]
+fn wrong_name_is_reported() {
+    let shaders = Shaders::compile(&VERTEX, &FRAGMENT).unwrap();
+    let details = details(shaders.check_vertex::<WrongName>());
+    assert!(details.contains("`position`"), "{}", details);
+    assert!(details.contains("`a_Pos`"), "{}", details);
+}
+
+#[test]
+fn missing_attribute_is_reported() {
+    let shaders = Shaders::compile(&VERTEX, &FRAGMENT).unwrap();
+    let details = details(shaders.check_vertex::<MissingAttribute>());
+    assert!(details.contains("declares 1 attributes"), "{}", details);
+}
+
+#[test]
+fn padding_between_fields_is_reported() {
+    let shaders = Shaders::compile(&VERTEX, &FRAGMENT).unwrap();
+    let details = details(shaders.check_vertex::<Padded>());
+    assert!(details.contains("`color` is at offset 1"), "{}", details);
+    assert!(details.contains("field at offset 4"), "{}", details);
+}
+
+#[test]
+fn format_override_replaces_the_inferred_format() {
+    assert_eq!(
+        vertex::attributes(&Overridden::vertex()),
+        vec![
+            AttributeDesc {
+                name: "a_Pos".into(),
+                format: Format::Rg32Sfloat,
+                offset: 0,
+            },
+            AttributeDesc {
+                name: "tint".into(),
+                format: Format::Rgba8Unorm,
+                offset: 8,
+            },
+        ]
+    );
+    assert_eq!(Overridden::vertex().stride, 12);
+}
+
+fn check_chapter<C: Chapter>(chapter: C) {
+    if let Err(err) = chapter.subpass::<Backend>() {
+        panic!("{:?}", err);
+    }
+}
+
+#[test]
+fn chapters_match_their_shaders() {
+    check_chapter(Chapter01);
+    check_chapter(Chapter02);
+    check_chapter(Chapter03);
+    check_chapter(Chapter03x1);
+    check_chapter(Chapter04);
+    check_chapter(Chapter05);
+    check_chapter(Chapter06::new().unwrap());
+}